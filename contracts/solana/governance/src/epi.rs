@@ -0,0 +1,99 @@
+//! Fixed-point port of the EPI core formula from `src/epi/calculator.py`:
+//!
+//! `EPI = H(P, E) × B(P, E) × T(V)`
+//!
+//! Every value is an integer scaled by `EPI_SCALE` (1.0 = 1_000_000). Each
+//! step rounds half up, so results stay within `EPI_TOLERANCE` units of the
+//! Python `EPICalculator` (default `phi_weight` of 1.0). The parity vectors in
+//! `tests/epi_vectors.txt` are generated from the Python code by
+//! `scripts/generate_epi_vectors.py` and checked on both sides.
+
+use crate::EPI_SCALE;
+
+/// Largest difference, in `EPI_SCALE` units, between these results and the
+/// Python floats scaled by `EPI_SCALE`. The parity vectors stay within one
+/// unit; the margin covers rounding compounding over long violation lists.
+pub const EPI_TOLERANCE: u64 = 4;
+
+const SCALE: u64 = EPI_SCALE as u64;
+/// Golden ratio conjugate, (√5 - 1) / 2
+const PHI: u64 = 618_034;
+/// Golden ratio, (1 + √5) / 2
+const GOLDEN_RATIO: u64 = 1_618_034;
+
+/// Components of an EPI computation, all scaled by `EPI_SCALE`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EpiComputation {
+    pub epi: u64,
+    pub harmonic_mean: u64,
+    pub balance_penalty: u64,
+    pub trust: u64,
+    /// `None` when ethics is zero, where the Python version returns infinity
+    pub golden_ratio_deviation: Option<u64>,
+}
+
+fn div_round(numerator: u128, denominator: u128) -> u64 {
+    ((numerator + denominator / 2) / denominator) as u64
+}
+
+/// `H = 2pe / (p + e)`, zero if either input is zero.
+pub fn harmonic_mean(profit: u64, ethics: u64) -> u64 {
+    if profit == 0 || ethics == 0 {
+        return 0;
+    }
+    div_round(2 * profit as u128 * ethics as u128, (profit + ethics) as u128)
+}
+
+/// `B = max(0, 1 - φ|p - e|)`.
+pub fn balance_penalty(profit: u64, ethics: u64) -> u64 {
+    let imbalance = profit.abs_diff(ethics);
+    SCALE.saturating_sub(div_round(PHI as u128 * imbalance as u128, SCALE as u128))
+}
+
+/// `T = Π(1 - vᵢ)`. With `floor` of zero this follows
+/// `EPICalculator.trust_accumulator`, dropping to zero below 1e-6; otherwise it
+/// follows `TrustAccumulator.compute_from_violations` and stops at `floor`.
+pub fn trust(violations: &[u64], floor: u64) -> u64 {
+    let mut trust = SCALE;
+    for severity in violations {
+        trust = div_round(
+            trust as u128 * SCALE.saturating_sub(*severity) as u128,
+            SCALE as u128,
+        );
+        if floor > 0 && trust < floor {
+            return floor;
+        }
+        if trust < 1 {
+            return 0;
+        }
+    }
+    trust
+}
+
+/// Distance of `p / e` from the nearer of φ and 1/φ.
+pub fn golden_ratio_deviation(profit: u64, ethics: u64) -> Option<u64> {
+    if ethics == 0 {
+        return None;
+    }
+    let ratio = div_round(profit as u128 * SCALE as u128, ethics as u128);
+    Some(ratio.abs_diff(GOLDEN_RATIO).min(ratio.abs_diff(PHI)))
+}
+
+/// Computes the EPI score from profit, ethics and violation severities.
+pub fn compute_epi(profit: u64, ethics: u64, violations: &[u64]) -> EpiComputation {
+    let harmonic_mean = harmonic_mean(profit, ethics);
+    let balance_penalty = balance_penalty(profit, ethics);
+    let trust = trust(violations, 0);
+    let epi = div_round(
+        div_round(harmonic_mean as u128 * balance_penalty as u128, SCALE as u128) as u128
+            * trust as u128,
+        SCALE as u128,
+    );
+    EpiComputation {
+        epi,
+        harmonic_mean,
+        balance_penalty,
+        trust,
+        golden_ratio_deviation: golden_ratio_deviation(profit, ethics),
+    }
+}
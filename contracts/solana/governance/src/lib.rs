@@ -1,7 +1,241 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+use membership::{Member, MemberRegistry, MemberType};
+
+pub mod epi;
 
 declare_id!("6amHFyNoPK9MmbBKqthLMeoxTB4TV7CdVE5K4RXi1eDC");
 
+pub const VOTE_RECORD_SEED: &[u8] = b"vote";
+/// The treasury PDA also acts as the DAO's signing authority for proposal instructions.
+pub const TREASURY_SEED: &[u8] = b"treasury";
+pub const PROPOSAL_INSTRUCTION_SEED: &[u8] = b"proposal-instruction";
+pub const DELEGATION_SEED: &[u8] = b"delegation";
+pub const PROPOSAL_OPTIONS_SEED: &[u8] = b"options";
+pub const DEPOSIT_SEED: &[u8] = b"deposit";
+pub const PROPOSAL_RULES_SEED: &[u8] = b"rules";
+pub const PROPOSAL_KIND_COUNT: usize = 4;
+pub const RISK_TIER_COUNT: usize = 4;
+pub const MAX_TITLE_LEN: usize = 128;
+pub const MAX_CONTENT_URI_LEN: usize = 200;
+/// Fixed-point scale for EPI scores: `EPI_SCALE` is 1.0
+pub const EPI_SCALE: u32 = 1_000_000;
+pub const MAX_EPI_VIOLATIONS: usize = 16;
+pub const COMPLIANCE_CHANGE_SEED: &[u8] = b"compliance";
+/// Longest value a compliance field can hold, after the 4-byte length prefix
+pub const MAX_COMPLIANCE_VALUE_LEN: usize = 508;
+
+pub const MAX_OPTIONS: usize = 4;
+pub const MAX_OPTION_LABEL_LEN: usize = 32;
+/// Padding for unused ranking slots.
+pub const NO_OPTION: u8 = u8::MAX;
+/// Every distinct full or partial ranking of `MAX_OPTIONS` options (4 + 12 + 24 + 24).
+pub const MAX_BALLOT_KINDS: usize = 64;
+
+pub const VOTE_RECORD_SPACE: usize = 8 + 1 + 1 + 32 + 8 + 32 + 1 + 1 + 32 + MAX_OPTIONS;
+
+pub const MAX_GUARDIANS: usize = 5;
+
+/// Denominator for all basis-point ratios in `GovernanceConfig`.
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Quadratic weights are `sqrt(voting_power * QUADRATIC_SCALE)`, i.e. the
+/// square root with three decimal places kept as a fixed-point integer.
+pub const QUADRATIC_SCALE: u128 = 1_000_000;
+
+/// One chamber per `MemberType`: Human, AI and Organization.
+pub const CHAMBER_COUNT: usize = 3;
+
+pub fn chamber_index(member_type: MemberType) -> usize {
+    match member_type {
+        MemberType::Human => 0,
+        MemberType::AI => 1,
+        MemberType::Organization => 2,
+    }
+}
+
+/// Floor of the square root, by Newton's method on integers only.
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    let mut x = n;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+/// Address of the vote record for `voter` on `proposal`. The account only
+/// exists once that voter has voted, so clients can use it as a has-voted check.
+pub fn find_vote_record_address(proposal: &Pubkey, voter: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VOTE_RECORD_SEED, proposal.as_ref(), voter.as_ref()],
+        &ID,
+    )
+}
+
+/// Records a vote for each delegator whose delegation names `delegate`.
+/// Delegators who already hold a vote record on the proposal are skipped,
+/// since their own vote (or an earlier delegated one) already counts.
+fn cast_delegated_votes<'info>(
+    proposal: &mut Account<'info, Proposal>,
+    choice: VoteChoice,
+    membership_registry: Pubkey,
+    delegate: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    let chunks = remaining_accounts.chunks_exact(3);
+    require!(chunks.remainder().is_empty(), ErrorCode::InvalidDelegationAccounts);
+    let proposal_key = proposal.key();
+
+    for accounts in chunks {
+        let delegation: Account<Delegation> = Account::try_from(&accounts[0])?;
+        let member: Account<Member> = Account::try_from(&accounts[1])?;
+        let record_info = &accounts[2];
+
+        require_keys_eq!(delegation.dao, proposal.dao, ErrorCode::InvalidDelegationAccounts);
+        require_keys_eq!(delegation.delegate, delegate.key(), ErrorCode::NotDelegate);
+        require_keys_eq!(
+            delegation.delegator_member,
+            member.key(),
+            ErrorCode::InvalidDelegationAccounts
+        );
+        require_keys_eq!(member.pubkey, delegation.delegator, ErrorCode::MemberMismatch);
+        require_keys_eq!(member.registry, membership_registry, ErrorCode::RegistryMismatch);
+
+        let (record_key, bump) = find_vote_record_address(&proposal_key, &delegation.delegator);
+        require_keys_eq!(record_info.key(), record_key, ErrorCode::InvalidDelegationAccounts);
+        if !record_info.data_is_empty() || !member.is_active || member.voting_power == 0 {
+            continue;
+        }
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            VOTE_RECORD_SEED,
+            proposal_key.as_ref(),
+            delegation.delegator.as_ref(),
+            &[bump],
+        ]];
+        create_pda_account(
+            record_info,
+            delegate,
+            system_program,
+            VOTE_RECORD_SPACE,
+            signer_seeds,
+        )?;
+
+        proposal.add_vote(member.member_type, choice, member.voting_power)?;
+
+        let record = VoteRecord {
+            has_voted: true,
+            choice,
+            voter: delegation.delegator,
+            voting_power: member.voting_power,
+            proposal: proposal_key,
+            bump,
+            member_type: member.member_type,
+            cast_by: delegate.key(),
+            ranking: [NO_OPTION; MAX_OPTIONS],
+        };
+        let mut data = record_info.try_borrow_mut_data()?;
+        record.try_serialize(&mut &mut data[..])?;
+    }
+
+    Ok(())
+}
+
+/// Loads vote records that `delegate` cast for its delegators on `proposal`.
+/// Records the delegator has since overridden with a direct vote are rejected.
+fn delegated_vote_records<'info>(
+    proposal: &Pubkey,
+    delegate: &Pubkey,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<Vec<Account<'info, VoteRecord>>> {
+    let mut records = Vec::with_capacity(remaining_accounts.len());
+    for (i, info) in remaining_accounts.iter().enumerate() {
+        require!(
+            !remaining_accounts[..i].iter().any(|other| other.key == info.key),
+            ErrorCode::InvalidDelegationAccounts
+        );
+        let record: Account<VoteRecord> = Account::try_from(info)?;
+        require_keys_eq!(record.proposal, *proposal, ErrorCode::InvalidDelegationAccounts);
+        require_keys_eq!(record.cast_by, *delegate, ErrorCode::NotDelegate);
+        require_keys_neq!(record.voter, *delegate, ErrorCode::InvalidDelegationAccounts);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Creates a program-owned PDA, tolerating lamports already sent to the address.
+fn create_pda_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    space: usize,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let required = Rent::get()?.minimum_balance(space);
+    let current = account.lamports();
+
+    if current == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+                signer_seeds,
+            ),
+            required,
+            space as u64,
+            &ID,
+        );
+    }
+
+    if current < required {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            required - current,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::Allocate {
+                account_to_allocate: account.clone(),
+            },
+            signer_seeds,
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::Assign {
+                account_to_assign: account.clone(),
+            },
+            signer_seeds,
+        ),
+        &ID,
+    )
+}
+
 #[program]
 pub mod governance {
     use super::*;
@@ -11,7 +245,13 @@ pub mod governance {
         legal_name: String,
         registered_agent_address: String,
         principal_place_of_business: String,
+        config: GovernanceConfig,
+        guardians: Vec<Pubkey>,
     ) -> Result<()> {
+        config.validate()?;
+        validate_guardians(&guardians)?;
+        validate_guardian_signoff(&config, &guardians)?;
+
         let dao = &mut ctx.accounts.dao;
         dao.authority = ctx.accounts.authority.key();
         dao.proposal_count = 0;
@@ -22,49 +262,891 @@ pub mod governance {
         dao.formation_date = Clock::get()?.unix_timestamp;
         dao.jurisdiction = "Wyoming".to_string();
         dao.entity_type = "DAO LLC".to_string();
+        dao.membership_registry = ctx.accounts.membership_registry.key();
+        dao.config = config;
+        dao.guardians = guardians;
+        dao.pending_authority = None;
+        Ok(())
+    }
+
+    /// Nominates a new authority, who must accept before it takes effect.
+    /// Passing `None` cancels a pending transfer.
+    pub fn propose_authority_transfer(
+        ctx: Context<UpdateAuthority>,
+        new_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.dao.pending_authority = new_authority;
+        Ok(())
+    }
+
+    pub fn accept_authority_transfer(ctx: Context<AcceptAuthorityTransfer>) -> Result<()> {
+        let dao = &mut ctx.accounts.dao;
+        require!(
+            dao.pending_authority == Some(ctx.accounts.new_authority.key()),
+            ErrorCode::NotPendingAuthority
+        );
+        dao.authority = ctx.accounts.new_authority.key();
+        dao.pending_authority = None;
+        Ok(())
+    }
+
+    /// Gives up the authority for good, leaving the DAO governed only by proposals.
+    pub fn renounce_authority(ctx: Context<UpdateAuthority>) -> Result<()> {
+        let dao = &mut ctx.accounts.dao;
+        dao.authority = Pubkey::default();
+        dao.pending_authority = None;
         Ok(())
     }
 
-    pub fn create_proposal(
-        ctx: Context<CreateProposal>,
-        title: String,
-        description: String,
-        amount: u64,
+    /// Sets the starting per-kind rules. Later changes go through a
+    /// `SetKindRules` proposal.
+    pub fn initialize_proposal_rules(
+        ctx: Context<InitializeProposalRules>,
+        rules: [KindRules; PROPOSAL_KIND_COUNT],
     ) -> Result<()> {
+        for kind_rules in rules.iter() {
+            kind_rules.validate()?;
+        }
+
+        let proposal_rules = &mut ctx.accounts.proposal_rules;
+        proposal_rules.dao = ctx.accounts.dao.key();
+        proposal_rules.rules = rules;
+        proposal_rules.bump = ctx.bumps.proposal_rules;
+        Ok(())
+    }
+
+    pub fn create_proposal(ctx: Context<CreateProposal>, args: CreateProposalArgs) -> Result<()> {
+        let CreateProposalArgs {
+            title,
+            content_uri,
+            content_hash,
+            amount,
+            recipient,
+            mint,
+            voting_starts_at,
+            voting_ends_at,
+            action,
+            kind,
+        } = args;
         let dao = &mut ctx.accounts.dao;
         let proposal = &mut ctx.accounts.proposal;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            !title.is_empty() && title.len() <= MAX_TITLE_LEN,
+            ErrorCode::InvalidTitle
+        );
+        require!(
+            !content_uri.is_empty() && content_uri.len() <= MAX_CONTENT_URI_LEN,
+            ErrorCode::InvalidContentUri
+        );
+        require!(voting_ends_at > voting_starts_at, ErrorCode::InvalidVotingWindow);
+        require!(voting_ends_at > now, ErrorCode::InvalidVotingWindow);
+        action.validate()?;
+        let kind = action.kind(amount, kind)?;
 
         proposal.id = dao.proposal_count;
         proposal.title = title;
-        proposal.description = description;
+        proposal.content_uri = content_uri;
+        proposal.content_hash = content_hash;
         proposal.amount = amount;
+        proposal.recipient = recipient;
+        proposal.mint = mint;
+        proposal.kind = kind;
+        proposal.signed_off_by = None;
         proposal.proposer = ctx.accounts.proposer.key();
         proposal.votes_for = 0;
         proposal.votes_against = 0;
+        proposal.votes_abstain = 0;
         proposal.status = ProposalStatus::Active;
-        proposal.created_at = Clock::get()?.unix_timestamp;
+        proposal.created_at = now;
+        proposal.voting_starts_at = voting_starts_at;
+        // Voting stays open at least as long as the kind and tier require,
+        // fixed now so later rule changes don't move the window
+        let kind_rules = ctx.accounts.proposal_rules.get(kind);
+        proposal.untiered_voting_ends_at =
+            voting_ends_at.max(voting_starts_at.saturating_add(kind_rules.voting_period));
+        // Proposals start in the strictest tier until a guardian assesses them
+        proposal.set_risk_tier(RiskTier::Critical, dao.config.tier(RiskTier::Critical));
+        proposal.dao = dao.key();
+        proposal.total_voting_power = ctx.accounts.membership_registry.total_voting_power;
+        proposal.action = action;
+        proposal.voting_strategy = kind_rules.voting_strategy;
+        proposal.participating_power = 0;
 
         dao.proposal_count += 1;
 
+        let escrow = &mut ctx.accounts.deposit_escrow;
+        escrow.proposal = proposal.key();
+        escrow.depositor = ctx.accounts.proposer.key();
+        escrow.amount = dao.config.proposal_deposit;
+        escrow.mint = dao.config.deposit_mint;
+        escrow.bump = ctx.bumps.deposit_escrow;
+
+        if escrow.amount == 0 {
+            return Ok(());
+        }
+        match escrow.mint {
+            None => system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.proposer.to_account_info(),
+                        to: escrow.to_account_info(),
+                    },
+                ),
+                escrow.amount,
+            ),
+            Some(mint_key) => {
+                let mint = ctx.accounts.deposit_mint.as_ref().ok_or(ErrorCode::MissingTokenAccounts)?;
+                let from = ctx
+                    .accounts
+                    .proposer_token_account
+                    .as_ref()
+                    .ok_or(ErrorCode::MissingTokenAccounts)?;
+                let to = ctx
+                    .accounts
+                    .escrow_token_account
+                    .as_ref()
+                    .ok_or(ErrorCode::MissingTokenAccounts)?;
+                let token_program = ctx
+                    .accounts
+                    .token_program
+                    .as_ref()
+                    .ok_or(ErrorCode::MissingTokenAccounts)?;
+
+                require_keys_eq!(mint.key(), mint_key, ErrorCode::MintMismatch);
+                require_keys_eq!(to.mint, mint_key, ErrorCode::MintMismatch);
+                require_keys_eq!(to.owner, escrow.key(), ErrorCode::InvalidEscrowTokenAccount);
+
+                token_interface::transfer_checked(
+                    CpiContext::new(
+                        token_program.to_account_info(),
+                        TransferChecked {
+                            from: from.to_account_info(),
+                            mint: mint.to_account_info(),
+                            to: to.to_account_info(),
+                            authority: ctx.accounts.proposer.to_account_info(),
+                        },
+                    ),
+                    escrow.amount,
+                    mint.decimals,
+                )
+            }
+        }
+    }
+
+    /// Returns the proposal deposit to the proposer if the proposal reached
+    /// quorum and wasn't flagged as spam, otherwise sends it to the treasury.
+    pub fn settle_deposit(ctx: Context<SettleDeposit>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        let escrow = &ctx.accounts.deposit_escrow;
+
+        require!(proposal.status != ProposalStatus::Active, ErrorCode::ProposalNotFinalized);
+        let refund = proposal.refunds_deposit();
+
+        if escrow.amount == 0 {
+            return Ok(());
+        }
+        match escrow.mint {
+            None => {
+                escrow.sub_lamports(escrow.amount)?;
+                if refund {
+                    ctx.accounts.proposer.add_lamports(escrow.amount)?;
+                } else {
+                    ctx.accounts.treasury.add_lamports(escrow.amount)?;
+                }
+            }
+            Some(mint_key) => {
+                let mint = ctx.accounts.mint.as_ref().ok_or(ErrorCode::MissingTokenAccounts)?;
+                let from = ctx
+                    .accounts
+                    .escrow_token_account
+                    .as_ref()
+                    .ok_or(ErrorCode::MissingTokenAccounts)?;
+                let to = ctx
+                    .accounts
+                    .destination_token_account
+                    .as_ref()
+                    .ok_or(ErrorCode::MissingTokenAccounts)?;
+                let token_program = ctx
+                    .accounts
+                    .token_program
+                    .as_ref()
+                    .ok_or(ErrorCode::MissingTokenAccounts)?;
+
+                let destination_owner = if refund {
+                    proposal.proposer
+                } else {
+                    ctx.accounts.treasury.key()
+                };
+                require_keys_eq!(mint.key(), mint_key, ErrorCode::MintMismatch);
+                require_keys_eq!(to.mint, mint_key, ErrorCode::MintMismatch);
+                require_keys_eq!(to.owner, destination_owner, ErrorCode::RecipientMismatch);
+
+                let proposal_key = proposal.key();
+                let signer_seeds: &[&[&[u8]]] =
+                    &[&[DEPOSIT_SEED, proposal_key.as_ref(), &[escrow.bump]]];
+                token_interface::transfer_checked(
+                    CpiContext::new_with_signer(
+                        token_program.to_account_info(),
+                        TransferChecked {
+                            from: from.to_account_info(),
+                            mint: mint.to_account_info(),
+                            to: to.to_account_info(),
+                            authority: escrow.to_account_info(),
+                        },
+                        signer_seeds,
+                    ),
+                    escrow.amount,
+                    mint.decimals,
+                )?;
+                // The emptied token account's rent goes back with the escrow's
+                token_interface::close_account(CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    CloseAccount {
+                        account: from.to_account_info(),
+                        destination: ctx.accounts.proposer.to_account_info(),
+                        authority: escrow.to_account_info(),
+                    },
+                    signer_seeds,
+                ))?;
+            }
+        }
+
         Ok(())
     }
 
-    pub fn vote(ctx: Context<Vote>, support: bool) -> Result<()> {
+    /// Casts the voter's own vote, plus one vote per delegation passed in
+    /// `remaining_accounts` as `[delegation, delegator member, delegator vote record]`.
+    pub fn vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Vote<'info>>,
+        choice: VoteChoice,
+    ) -> Result<()> {
         let proposal = &mut ctx.accounts.proposal;
         let vote_record = &mut ctx.accounts.vote_record;
+        let voter = ctx.accounts.voter.key();
 
-        require!(proposal.status == ProposalStatus::Active, ErrorCode::ProposalNotActive);
-        require!(!vote_record.has_voted, ErrorCode::AlreadyVoted);
+        proposal.require_voting_open(Clock::get()?.unix_timestamp)?;
+        require!(proposal.option_count == 0, ErrorCode::NotYesNoProposal);
+        if vote_record.has_voted {
+            // A direct vote overrides one a delegate cast on the voter's behalf
+            require!(vote_record.cast_by != voter, ErrorCode::AlreadyVoted);
+            proposal.remove_vote(vote_record.member_type, vote_record.choice, vote_record.voting_power)?;
+        }
+
+        // Snapshot the member's power so later membership changes don't move the tally
+        let voting_power = ctx.accounts.member.voting_power;
+        require!(voting_power > 0, ErrorCode::NoVotingPower);
+
+        let member_type = ctx.accounts.member.member_type;
+        proposal.add_vote(member_type, choice, voting_power)?;
+
+        vote_record.has_voted = true;
+        vote_record.choice = choice;
+        vote_record.voter = voter;
+        vote_record.voting_power = voting_power;
+        vote_record.member_type = member_type;
+        vote_record.proposal = proposal.key();
+        vote_record.bump = ctx.bumps.vote_record;
+        vote_record.cast_by = voter;
+        vote_record.ranking = [NO_OPTION; MAX_OPTIONS];
+
+        cast_delegated_votes(
+            proposal,
+            choice,
+            ctx.accounts.dao.membership_registry,
+            &ctx.accounts.voter,
+            &ctx.accounts.system_program,
+            ctx.remaining_accounts,
+        )
+    }
+
+    /// Changes the voter's choice. A delegate passes the vote records it cast
+    /// for its delegators in `remaining_accounts`, and those follow the change.
+    pub fn change_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ChangeVote<'info>>,
+        choice: VoteChoice,
+    ) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        let vote_record = &mut ctx.accounts.vote_record;
+
+        proposal.require_voting_open(Clock::get()?.unix_timestamp)?;
+        require!(proposal.option_count == 0, ErrorCode::NotYesNoProposal);
+
+        // Move the snapshotted power; the member's current power is not re-read
+        proposal.remove_vote(vote_record.member_type, vote_record.choice, vote_record.voting_power)?;
+        proposal.add_vote(vote_record.member_type, choice, vote_record.voting_power)?;
+        vote_record.choice = choice;
+
+        let proposal_key = proposal.key();
+        for mut record in delegated_vote_records(&proposal_key, &vote_record.voter, ctx.remaining_accounts)? {
+            proposal.remove_vote(record.member_type, record.choice, record.voting_power)?;
+            proposal.add_vote(record.member_type, choice, record.voting_power)?;
+            record.choice = choice;
+            record.exit(&ID)?;
+        }
+
+        Ok(())
+    }
+
+    /// Withdraws the voter's vote. A delegate passes the vote records it cast
+    /// for its delegators in `remaining_accounts`; those are withdrawn too and
+    /// their rent returned to the delegate, who paid for them.
+    pub fn withdraw_vote<'info>(ctx: Context<'_, '_, 'info, 'info, WithdrawVote<'info>>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        let vote_record = &ctx.accounts.vote_record;
 
-        if support {
-            proposal.votes_for += 1;
+        proposal.require_voting_open(Clock::get()?.unix_timestamp)?;
+
+        if proposal.option_count == 0 {
+            proposal.remove_vote(vote_record.member_type, vote_record.choice, vote_record.voting_power)?;
         } else {
-            proposal.votes_against += 1;
+            let options = ctx.accounts.options.as_mut().ok_or(ErrorCode::MissingProposalOptions)?;
+            options.remove_ballot(
+                &vote_record.ranking,
+                proposal.voting_strategy.weight(vote_record.voting_power),
+            )?;
+            proposal.participating_power = proposal
+                .participating_power
+                .checked_sub(vote_record.voting_power)
+                .ok_or(ErrorCode::TallyUnderflow)?;
+        }
+
+        // Delegated votes are only cast on yes/no proposals
+        let proposal_key = proposal.key();
+        for record in delegated_vote_records(&proposal_key, &vote_record.voter, ctx.remaining_accounts)? {
+            proposal.remove_vote(record.member_type, record.choice, record.voting_power)?;
+            record.close(ctx.accounts.voter.to_account_info())?;
         }
 
+        Ok(())
+    }
+
+    pub fn create_proposal_options(
+        ctx: Context<CreateProposalOptions>,
+        labels: Vec<String>,
+        method: TallyMethod,
+    ) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+
+        require!(proposal.status == ProposalStatus::Active, ErrorCode::ProposalNotActive);
+        require!(
+            Clock::get()?.unix_timestamp < proposal.voting_starts_at,
+            ErrorCode::VotingAlreadyStarted
+        );
+        // Options pass without the yes/no thresholds, so they may only branch
+        // through option-tagged instructions, which are checked against the
+        // options as they're inserted
+        require!(
+            proposal.action == ProposalAction::None
+                && proposal.amount == 0
+                && proposal.instruction_count == 0,
+            ErrorCode::InvalidOptionProposal
+        );
+        require!(
+            (2..=MAX_OPTIONS).contains(&labels.len()),
+            ErrorCode::InvalidProposalOptions
+        );
+        require!(
+            labels.iter().all(|label| label.len() <= MAX_OPTION_LABEL_LEN),
+            ErrorCode::InvalidProposalOptions
+        );
+
+        proposal.option_count = labels.len() as u8;
+
+        let options = &mut ctx.accounts.options;
+        options.proposal = proposal.key();
+        options.method = method;
+        options.labels = labels;
+        options.ballots = Vec::new();
+        options.bump = ctx.bumps.options;
+
+        Ok(())
+    }
+
+    /// Votes on a multi-option proposal. Plurality takes a single option;
+    /// ranked choice takes options in order of preference.
+    pub fn vote_options(ctx: Context<VoteOptions>, ranking: Vec<u8>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        let options = &mut ctx.accounts.options;
+        let vote_record = &mut ctx.accounts.vote_record;
+
+        proposal.require_voting_open(Clock::get()?.unix_timestamp)?;
+        let ranking = options.validate_ranking(&ranking)?;
+
+        let voting_power = ctx.accounts.member.voting_power;
+        require!(voting_power > 0, ErrorCode::NoVotingPower);
+
+        options.add_ballot(&ranking, proposal.voting_strategy.weight(voting_power))?;
+        proposal.participating_power = proposal
+            .participating_power
+            .checked_add(voting_power)
+            .ok_or(ErrorCode::TallyOverflow)?;
+
+        let voter = ctx.accounts.voter.key();
         vote_record.has_voted = true;
-        vote_record.support = support;
-        vote_record.voter = ctx.accounts.voter.key();
+        vote_record.choice = VoteChoice::For;
+        vote_record.voter = voter;
+        vote_record.voting_power = voting_power;
+        vote_record.member_type = ctx.accounts.member.member_type;
+        vote_record.proposal = proposal.key();
+        vote_record.bump = ctx.bumps.vote_record;
+        vote_record.cast_by = voter;
+        vote_record.ranking = ranking;
+
+        Ok(())
+    }
+
+    pub fn delegate(ctx: Context<Delegate>) -> Result<()> {
+        let delegation = &mut ctx.accounts.delegation;
+        delegation.dao = ctx.accounts.dao.key();
+        delegation.delegator = ctx.accounts.delegator.key();
+        delegation.delegator_member = ctx.accounts.delegator_member.key();
+        delegation.delegate = ctx.accounts.delegate_member.pubkey;
+        delegation.created_at = Clock::get()?.unix_timestamp;
+        delegation.bump = ctx.bumps.delegation;
+        Ok(())
+    }
+
+    pub fn revoke_delegation(_ctx: Context<RevokeDelegation>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+        let config = &ctx.accounts.dao.config;
+        let proposal = &mut ctx.accounts.proposal;
+        let rules = ctx.accounts.proposal_rules.get(proposal.kind);
+
+        require!(proposal.status == ProposalStatus::Active, ErrorCode::ProposalNotActive);
+        require!(
+            Clock::get()?.unix_timestamp >= proposal.voting_ends_at,
+            ErrorCode::VotingStillOpen
+        );
+
+        // The DAO-wide config acts as a floor under the per-kind and per-tier rules
+        let tier = config.tier(proposal.risk_tier);
+        let quorum_bps = config.quorum_bps.max(rules.quorum_bps).max(tier.quorum_bps);
+
+        // Quorum is measured in raw voting power whatever the strategy, and
+        // abstentions count toward it but not toward approval
+        let quorum_reached = proposal.participating_power as u128 * BPS_DENOMINATOR as u128
+            >= proposal.total_voting_power as u128 * quorum_bps as u128;
+        proposal.quorum_reached = quorum_reached;
+
+        let mut approval_threshold_bps = config.approval_threshold_bps.max(rules.approval_threshold_bps);
+        if tier.requires_supermajority {
+            approval_threshold_bps = approval_threshold_bps.max(config.supermajority_bps);
+        }
+
+        // Multi-option proposals pass with whichever option wins the count. If
+        // the winner runs treasury-signed instructions it must also be the
+        // first preference of a supermajority
+        if proposal.option_count > 0 {
+            let options = ctx.accounts.options.as_ref().ok_or(ErrorCode::MissingProposalOptions)?;
+            let threshold_bps = config.supermajority_bps.max(approval_threshold_bps);
+            let winner = if quorum_reached { options.winner() } else { None }.filter(|option| {
+                proposal.instruction_count == 0
+                    || options.first_preference_bps(*option) > threshold_bps as u64
+            });
+            proposal.winning_option = winner;
+            proposal.status = if winner.is_some() {
+                ProposalStatus::Passed
+            } else {
+                ProposalStatus::Rejected
+            };
+            return Ok(());
+        }
+
+        // Changes to the governance rules themselves, and arbitrary
+        // treasury-signed instructions, need a supermajority
+        let threshold_bps = match proposal.action {
+            _ if proposal.instruction_count > 0 => config.supermajority_bps.max(approval_threshold_bps),
+            ProposalAction::UpdateConfig(_)
+            | ProposalAction::SetGuardians(_)
+            | ProposalAction::SetKindRules { .. } => {
+                config.supermajority_bps.max(approval_threshold_bps)
+            }
+            ProposalAction::None | ProposalAction::UpdateCompliance { .. } => approval_threshold_bps,
+        };
+
+        let approved = match config.passage_mode {
+            PassageMode::WeightedMajority => {
+                proposal.weighted_approval_bps(&config.chamber_weights_bps) > threshold_bps as u64
+            }
+            PassageMode::ChamberMajority { required } => {
+                proposal.chambers_approving(threshold_bps) >= required
+            }
+        } && (0..CHAMBER_COUNT)
+            .filter(|chamber| rules.required_chambers & (1 << chamber) != 0)
+            .all(|chamber| proposal.chamber_approves(chamber, threshold_bps));
+
+        proposal.status = if quorum_reached && approved {
+            ProposalStatus::Passed
+        } else {
+            ProposalStatus::Rejected
+        };
+
+        Ok(())
+    }
+
+    /// Records the scorer's EPI inputs on a proposal, replacing any earlier
+    /// attestation. The EPI itself is recomputed on-chain from the inputs.
+    /// Attestations are frozen once voting closes, so the score checked at
+    /// execution is the one voters saw.
+    pub fn attest_epi(
+        ctx: Context<AttestEpi>,
+        profit_score: u32,
+        ethics_score: u32,
+        violations: Vec<u32>,
+        inputs_hash: [u8; 32],
+    ) -> Result<()> {
+        let scorer = ctx.accounts.scorer.key();
+        let proposal = &mut ctx.accounts.proposal;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            ctx.accounts.dao.config.epi_scorer == Some(scorer),
+            ErrorCode::NotEpiScorer
+        );
+        require!(proposal.status == ProposalStatus::Active, ErrorCode::ProposalClosed);
+        require!(now < proposal.voting_ends_at, ErrorCode::VotingClosed);
+        require!(
+            profit_score <= EPI_SCALE && ethics_score <= EPI_SCALE,
+            ErrorCode::InvalidEpiScore
+        );
+        require!(
+            violations.len() <= MAX_EPI_VIOLATIONS
+                && violations.iter().all(|severity| *severity <= EPI_SCALE),
+            ErrorCode::InvalidEpiScore
+        );
+
+        let severities: Vec<u64> = violations.iter().map(|severity| *severity as u64).collect();
+        let epi_score =
+            epi::compute_epi(profit_score as u64, ethics_score as u64, &severities).epi as u32;
+
+        proposal.epi_attestation = Some(EpiAttestation {
+            epi_score,
+            profit_score,
+            ethics_score,
+            violations,
+            inputs_hash,
+            scorer,
+            attested_at: now,
+        });
+
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.depositor.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                },
+            ),
+            amount,
+        )
+    }
+
+    pub fn create_treasury_token_account(_ctx: Context<CreateTreasuryTokenAccount>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn queue_proposal(ctx: Context<QueueProposal>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+
+        require!(proposal.status == ProposalStatus::Passed, ErrorCode::ProposalNotPassed);
+        if ctx.accounts.dao.config.tier(proposal.risk_tier).requires_guardian_signoff {
+            require!(proposal.signed_off_by.is_some(), ErrorCode::MissingGuardianSignoff);
+        }
+
+        proposal.eta = Clock::get()?
+            .unix_timestamp
+            .checked_add(ctx.accounts.dao.config.timelock_delay)
+            .ok_or(ErrorCode::InvalidGovernanceConfig)?;
+        proposal.status = ProposalStatus::Queued;
+
+        Ok(())
+    }
+
+    /// Sets a proposal's risk tier. Proposals start out `Critical`; a guardian
+    /// may move one to any tier before voting starts, and guardians or the
+    /// EPI scorer may raise it until voting ends.
+    pub fn set_risk_tier(ctx: Context<SetRiskTier>, risk_tier: RiskTier) -> Result<()> {
+        let dao = &ctx.accounts.dao;
+        let assessor = ctx.accounts.assessor.key();
+        let proposal = &mut ctx.accounts.proposal;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(proposal.status == ProposalStatus::Active, ErrorCode::ProposalNotActive);
+        if (risk_tier as u8) < (proposal.risk_tier as u8) {
+            require!(dao.guardians.contains(&assessor), ErrorCode::NotGuardian);
+            require!(now < proposal.voting_starts_at, ErrorCode::VotingAlreadyStarted);
+        } else {
+            require!(
+                dao.guardians.contains(&assessor) || dao.config.epi_scorer == Some(assessor),
+                ErrorCode::NotRiskAssessor
+            );
+            require!(now < proposal.voting_ends_at, ErrorCode::VotingClosed);
+        }
+
+        proposal.set_risk_tier(risk_tier, dao.config.tier(risk_tier));
+        Ok(())
+    }
+
+    /// Guardian approval of a passed proposal, required before queueing in
+    /// tiers that ask for it.
+    pub fn sign_off_proposal(ctx: Context<SignOffProposal>) -> Result<()> {
+        let guardian = ctx.accounts.guardian.key();
+        require!(
+            ctx.accounts.dao.guardians.contains(&guardian),
+            ErrorCode::NotGuardian
+        );
+
+        let proposal = &mut ctx.accounts.proposal;
+        require!(proposal.status == ProposalStatus::Passed, ErrorCode::ProposalNotPassed);
+        proposal.signed_off_by = Some(guardian);
+
+        Ok(())
+    }
+
+    /// Vetoes a proposal. Flagging it as spam also forfeits the proposer's deposit.
+    pub fn veto_proposal(ctx: Context<VetoProposal>, reason_hash: [u8; 32], spam: bool) -> Result<()> {
+        let guardian = ctx.accounts.guardian.key();
+        require!(
+            ctx.accounts.dao.guardians.contains(&guardian),
+            ErrorCode::NotGuardian
+        );
+
+        let proposal = &mut ctx.accounts.proposal;
+        require!(
+            matches!(
+                proposal.status,
+                ProposalStatus::Active | ProposalStatus::Passed | ProposalStatus::Queued
+            ),
+            ErrorCode::ProposalNotVetoable
+        );
+
+        proposal.status = ProposalStatus::Vetoed;
+        proposal.vetoed_by = guardian;
+        proposal.veto_reason_hash = reason_hash;
+        proposal.flagged_spam = spam;
+
+        Ok(())
+    }
+
+    pub fn insert_instruction(
+        ctx: Context<InsertInstruction>,
+        program_id: Pubkey,
+        accounts: Vec<ProposalAccountMeta>,
+        data: Vec<u8>,
+        option: Option<u8>,
+    ) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+
+        require!(proposal.status == ProposalStatus::Active, ErrorCode::ProposalNotActive);
+        // Instructions are frozen once voting opens so voters see the final payload
+        require!(
+            Clock::get()?.unix_timestamp < proposal.voting_starts_at,
+            ErrorCode::VotingAlreadyStarted
+        );
+        proposal.validate_instruction(option)?;
+
+        let instruction = &mut ctx.accounts.proposal_instruction;
+        instruction.proposal = proposal.key();
+        instruction.index = proposal.instruction_count;
+        instruction.program_id = program_id;
+        instruction.accounts = accounts;
+        instruction.data = data;
+        instruction.executed = false;
+        instruction.bump = ctx.bumps.proposal_instruction;
+        instruction.option = option;
+
+        proposal.instruction_count = proposal
+            .instruction_count
+            .checked_add(1)
+            .ok_or(ErrorCode::TooManyInstructions)?;
+
+        Ok(())
+    }
+
+    pub fn execute_instruction<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteInstruction<'info>>,
+    ) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        let instruction = &mut ctx.accounts.proposal_instruction;
+
+        proposal.require_executable(Clock::get()?.unix_timestamp)?;
+        proposal.require_epi_floor(ctx.accounts.dao.config.epi_floor)?;
+        require!(!instruction.executed, ErrorCode::InstructionAlreadyExecuted);
+        require!(
+            instruction.index == proposal.instructions_executed,
+            ErrorCode::InstructionOutOfOrder
+        );
+
+        // Instructions tied to an option that lost are skipped, not run
+        if instruction.option.is_some() && instruction.option != proposal.winning_option {
+            proposal.instructions_executed += 1;
+            return Ok(());
+        }
+
+        let ix = Instruction {
+            program_id: instruction.program_id,
+            accounts: instruction
+                .accounts
+                .iter()
+                .map(|meta| AccountMeta {
+                    pubkey: meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: instruction.data.clone(),
+        };
+
+        let dao_key = ctx.accounts.dao.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[TREASURY_SEED, dao_key.as_ref(), &[ctx.bumps.treasury]]];
+        let mut account_infos = ctx.remaining_accounts.to_vec();
+        account_infos.push(ctx.accounts.treasury.to_account_info());
+        invoke_signed(&ix, &account_infos, signer_seeds)?;
+
+        instruction.executed = true;
+        proposal.instructions_executed += 1;
+
+        Ok(())
+    }
+
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        let dao = &mut ctx.accounts.dao;
+        let proposal = &mut ctx.accounts.proposal;
+
+        proposal.require_executable(Clock::get()?.unix_timestamp)?;
+        proposal.require_epi_floor(dao.config.epi_floor)?;
+        require!(
+            proposal.instructions_executed == proposal.instruction_count,
+            ErrorCode::InstructionsPending
+        );
+        if let Some(max_amount) = ctx.accounts.proposal_rules.get(proposal.kind).max_amount {
+            require!(proposal.amount <= max_amount, ErrorCode::AmountExceedsKindLimit);
+        }
+
+        if proposal.amount > 0 {
+            let dao_key = dao.key();
+            let signer_seeds: &[&[&[u8]]] =
+                &[&[TREASURY_SEED, dao_key.as_ref(), &[ctx.bumps.treasury]]];
+
+            match proposal.mint {
+                None => {
+                    require!(
+                        ctx.accounts.treasury.lamports() >= proposal.amount,
+                        ErrorCode::InsufficientTreasuryFunds
+                    );
+
+                    system_program::transfer(
+                        CpiContext::new_with_signer(
+                            ctx.accounts.system_program.to_account_info(),
+                            system_program::Transfer {
+                                from: ctx.accounts.treasury.to_account_info(),
+                                to: ctx.accounts.recipient.to_account_info(),
+                            },
+                            signer_seeds,
+                        ),
+                        proposal.amount,
+                    )?;
+                }
+                Some(mint_key) => {
+                    let mint = ctx.accounts.mint.as_ref().ok_or(ErrorCode::MissingTokenAccounts)?;
+                    let from = ctx
+                        .accounts
+                        .treasury_token_account
+                        .as_ref()
+                        .ok_or(ErrorCode::MissingTokenAccounts)?;
+                    let to = ctx
+                        .accounts
+                        .recipient_token_account
+                        .as_ref()
+                        .ok_or(ErrorCode::MissingTokenAccounts)?;
+                    let token_program = ctx
+                        .accounts
+                        .token_program
+                        .as_ref()
+                        .ok_or(ErrorCode::MissingTokenAccounts)?;
+
+                    require_keys_eq!(mint.key(), mint_key, ErrorCode::MintMismatch);
+                    require_keys_eq!(from.mint, mint_key, ErrorCode::MintMismatch);
+                    require_keys_eq!(to.mint, mint_key, ErrorCode::MintMismatch);
+                    require_keys_eq!(
+                        from.owner,
+                        ctx.accounts.treasury.key(),
+                        ErrorCode::InvalidTreasuryTokenAccount
+                    );
+                    require_keys_eq!(to.owner, proposal.recipient, ErrorCode::RecipientMismatch);
+                    require!(from.amount >= proposal.amount, ErrorCode::InsufficientTreasuryFunds);
+
+                    token_interface::transfer_checked(
+                        CpiContext::new_with_signer(
+                            token_program.to_account_info(),
+                            TransferChecked {
+                                from: from.to_account_info(),
+                                mint: mint.to_account_info(),
+                                to: to.to_account_info(),
+                                authority: ctx.accounts.treasury.to_account_info(),
+                            },
+                            signer_seeds,
+                        ),
+                        proposal.amount,
+                        mint.decimals,
+                    )?;
+                }
+            }
+        }
+
+        match &proposal.action {
+            ProposalAction::None => {}
+            ProposalAction::UpdateCompliance { field, value } => {
+                let slot = match field {
+                    ComplianceField::LegalName => &mut dao.legal_name,
+                    ComplianceField::RegisteredAgentAddress => &mut dao.registered_agent_address,
+                    ComplianceField::PrincipalPlaceOfBusiness => &mut dao.principal_place_of_business,
+                };
+                let old_value = std::mem::replace(slot, value.clone());
+
+                let change = ctx
+                    .accounts
+                    .compliance_change
+                    .as_mut()
+                    .ok_or(ErrorCode::MissingComplianceChange)?;
+                change.dao = dao.key();
+                change.proposal = proposal.key();
+                change.proposal_id = proposal.id;
+                change.field = *field;
+                change.old_value = old_value;
+                change.new_value = value.clone();
+                change.effective_at = Clock::get()?.unix_timestamp;
+                change.bump = ctx.bumps.compliance_change.ok_or(ErrorCode::MissingComplianceChange)?;
+            }
+            ProposalAction::UpdateConfig(config) => {
+                config.validate()?;
+                validate_guardian_signoff(config, &dao.guardians)?;
+                dao.config = config.clone();
+            }
+            ProposalAction::SetGuardians(guardians) => {
+                validate_guardians(guardians)?;
+                validate_guardian_signoff(&dao.config, guardians)?;
+                dao.guardians = guardians.clone();
+            }
+            ProposalAction::SetKindRules { kind, rules } => {
+                rules.validate()?;
+                ctx.accounts.proposal_rules.rules[*kind as usize] = rules.clone();
+            }
+        }
+
+        proposal.status = ProposalStatus::Executed;
 
         Ok(())
     }
@@ -72,67 +1154,1125 @@ pub mod governance {
 
 #[derive(Accounts)]
 pub struct Initialize<'info> {
-    #[account(init, payer = authority, space = 8 + 32 + 8 + 8 + 256 + 512 + 512 + 8 + 64 + 64)]
+    #[account(init, payer = authority, space = 8 + 32 + 8 + 8 + 256 + 512 + 512 + 8 + 64 + 64 + 32 + 148 + 4 + 32 * MAX_GUARDIANS + 33)]
     pub dao: Account<'info, Dao>,
+    pub membership_registry: Account<'info, MemberRegistry>,
     #[account(mut)]
     pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct CreateProposal<'info> {
+pub struct UpdateAuthority<'info> {
+    #[account(mut, has_one = authority)]
+    pub dao: Account<'info, Dao>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthorityTransfer<'info> {
     #[account(mut)]
     pub dao: Account<'info, Dao>,
-    #[account(init, payer = proposer, space = 8 + 8 + 256 + 512 + 8 + 32 + 8 + 8 + 1 + 8)]
-    pub proposal: Account<'info, Proposal>,
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeProposalRules<'info> {
+    #[account(has_one = authority)]
+    pub dao: Account<'info, Dao>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + PROPOSAL_KIND_COUNT * (2 + 2 + 8 + 9 + 1 + 1) + 1,
+        seeds = [PROPOSAL_RULES_SEED, dao.key().as_ref()],
+        bump
+    )]
+    pub proposal_rules: Account<'info, ProposalRules>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(mut, has_one = membership_registry)]
+    pub dao: Box<Account<'info, Dao>>,
+    #[account(init, payer = proposer, space = 8 + 8 + (4 + MAX_TITLE_LEN) + (4 + MAX_CONTENT_URI_LEN) + 32 + 8 + 32 + 8 + 8 + 1 + 8 + 8 + 8 + 32 + 8 + (1 + 1 + 4 + MAX_COMPLIANCE_VALUE_LEN) + 32 + 33 + 72 + 2 + 2 + 8 + 32 + 32 + 8 + 1 + 8 + 1 + 2 + 1 + 1 + 1 + (1 + 4 * 3 + 4 + 4 * MAX_EPI_VIOLATIONS + 32 + 32 + 8) + 1 + 33 + 8)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    pub membership_registry: Account<'info, MemberRegistry>,
+    #[account(seeds = [PROPOSAL_RULES_SEED, dao.key().as_ref()], bump = proposal_rules.bump)]
+    pub proposal_rules: Account<'info, ProposalRules>,
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + 32 + 32 + 8 + 33 + 1,
+        seeds = [DEPOSIT_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub deposit_escrow: Account<'info, DepositEscrow>,
     #[account(mut)]
     pub proposer: Signer<'info>,
     pub system_program: Program<'info, System>,
+    // Token accounts are only required when the DAO takes deposits in a token
+    pub deposit_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub proposer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub escrow_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 #[derive(Accounts)]
-pub struct Vote<'info> {
+pub struct SettleDeposit<'info> {
+    pub dao: Account<'info, Dao>,
+    #[account(has_one = dao, has_one = proposer)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [DEPOSIT_SEED, proposal.key().as_ref()],
+        bump = deposit_escrow.bump,
+        has_one = proposal,
+    )]
+    pub deposit_escrow: Account<'info, DepositEscrow>,
+    /// CHECK: Must match `proposal.proposer`; receives refunds and the escrow rent
     #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+    #[account(mut, seeds = [TREASURY_SEED, dao.key().as_ref()], bump)]
+    pub treasury: SystemAccount<'info>,
+    // Token accounts are only required for token deposits. The destination
+    // belongs to the proposer on refund and to the treasury when slashed.
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub escrow_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub destination_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct Vote<'info> {
+    pub dao: Box<Account<'info, Dao>>,
+    #[account(mut, has_one = dao)]
     pub proposal: Account<'info, Proposal>,
-    #[account(init, payer = voter, space = 8 + 1 + 1 + 32)]
+    // May already exist when a delegate voted on the voter's behalf
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = VOTE_RECORD_SPACE,
+        seeds = [VOTE_RECORD_SEED, proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
     pub vote_record: Account<'info, VoteRecord>,
+    // Owner is checked against the membership program by `Account`
+    #[account(
+        constraint = member.pubkey == voter.key() @ ErrorCode::MemberMismatch,
+        constraint = member.registry == dao.membership_registry @ ErrorCode::RegistryMismatch,
+        constraint = member.is_active @ ErrorCode::MemberInactive,
+    )]
+    pub member: Account<'info, Member>,
     #[account(mut)]
     pub voter: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
-#[account]
-pub struct Dao {
-    pub authority: Pubkey,
-    pub proposal_count: u64,
-    pub member_count: u64,
-    // Wyoming DAO LLC Compliance Fields
+#[derive(Accounts)]
+pub struct Delegate<'info> {
+    pub dao: Account<'info, Dao>,
+    #[account(
+        init,
+        payer = delegator,
+        space = 8 + 32 + 32 + 32 + 32 + 8 + 1,
+        seeds = [DELEGATION_SEED, dao.key().as_ref(), delegator.key().as_ref()],
+        bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+    #[account(
+        constraint = delegator_member.pubkey == delegator.key() @ ErrorCode::MemberMismatch,
+        constraint = delegator_member.registry == dao.membership_registry @ ErrorCode::RegistryMismatch,
+        constraint = delegator_member.is_active @ ErrorCode::MemberInactive,
+    )]
+    pub delegator_member: Account<'info, Member>,
+    #[account(
+        constraint = delegate_member.pubkey != delegator.key() @ ErrorCode::SelfDelegation,
+        constraint = delegate_member.registry == dao.membership_registry @ ErrorCode::RegistryMismatch,
+        constraint = delegate_member.is_active @ ErrorCode::MemberInactive,
+    )]
+    pub delegate_member: Account<'info, Member>,
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegation<'info> {
+    #[account(
+        mut,
+        close = delegator,
+        seeds = [DELEGATION_SEED, delegation.dao.as_ref(), delegator.key().as_ref()],
+        bump = delegation.bump,
+        has_one = delegator,
+    )]
+    pub delegation: Account<'info, Delegation>,
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ChangeVote<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        mut,
+        seeds = [VOTE_RECORD_SEED, proposal.key().as_ref(), voter.key().as_ref()],
+        bump = vote_record.bump,
+        has_one = voter,
+        has_one = proposal,
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    pub voter: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawVote<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+    // Closing the record lets the member vote again while the window is open
+    #[account(
+        mut,
+        close = voter,
+        seeds = [VOTE_RECORD_SEED, proposal.key().as_ref(), voter.key().as_ref()],
+        bump = vote_record.bump,
+        has_one = voter,
+        has_one = proposal,
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    // Required when withdrawing from a multi-option proposal
+    #[account(
+        mut,
+        seeds = [PROPOSAL_OPTIONS_SEED, proposal.key().as_ref()],
+        bump = options.bump,
+    )]
+    pub options: Option<Account<'info, ProposalOptions>>,
+}
+
+#[derive(Accounts)]
+#[instruction(labels: Vec<String>)]
+pub struct CreateProposalOptions<'info> {
+    #[account(mut, has_one = proposer)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + 32 + 1 + 4 + labels.len() * (4 + MAX_OPTION_LABEL_LEN) + 4
+            + MAX_BALLOT_KINDS * (MAX_OPTIONS + 8) + 1,
+        seeds = [PROPOSAL_OPTIONS_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub options: Account<'info, ProposalOptions>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VoteOptions<'info> {
+    pub dao: Box<Account<'info, Dao>>,
+    #[account(mut, has_one = dao)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        mut,
+        seeds = [PROPOSAL_OPTIONS_SEED, proposal.key().as_ref()],
+        bump = options.bump,
+    )]
+    pub options: Account<'info, ProposalOptions>,
+    #[account(
+        init,
+        payer = voter,
+        space = VOTE_RECORD_SPACE,
+        seeds = [VOTE_RECORD_SEED, proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    #[account(
+        constraint = member.pubkey == voter.key() @ ErrorCode::MemberMismatch,
+        constraint = member.registry == dao.membership_registry @ ErrorCode::RegistryMismatch,
+        constraint = member.is_active @ ErrorCode::MemberInactive,
+    )]
+    pub member: Account<'info, Member>,
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeProposal<'info> {
+    pub dao: Account<'info, Dao>,
+    #[account(mut, has_one = dao)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(seeds = [PROPOSAL_RULES_SEED, dao.key().as_ref()], bump = proposal_rules.bump)]
+    pub proposal_rules: Account<'info, ProposalRules>,
+    // Required for multi-option proposals
+    #[account(
+        seeds = [PROPOSAL_OPTIONS_SEED, proposal.key().as_ref()],
+        bump = options.bump,
+    )]
+    pub options: Option<Account<'info, ProposalOptions>>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(mut)]
+    pub dao: Box<Account<'info, Dao>>,
+    #[account(mut, has_one = dao, has_one = recipient)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(mut, seeds = [PROPOSAL_RULES_SEED, dao.key().as_ref()], bump = proposal_rules.bump)]
+    pub proposal_rules: Account<'info, ProposalRules>,
+    #[account(mut, seeds = [TREASURY_SEED, dao.key().as_ref()], bump)]
+    pub treasury: SystemAccount<'info>,
+    /// CHECK: Must match `proposal.recipient`; only receives lamports
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    // Token accounts are only required when the proposal names a mint
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub recipient_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    // Required for `UpdateCompliance` proposals, to record the change
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 32 + 32 + 8 + 1 + 2 * (4 + MAX_COMPLIANCE_VALUE_LEN) + 8 + 1,
+        seeds = [COMPLIANCE_CHANGE_SEED, dao.key().as_ref(), proposal.key().as_ref()],
+        bump
+    )]
+    pub compliance_change: Option<Account<'info, ComplianceChange>>,
+    #[account(mut)]
+    pub payer: Option<Signer<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct AttestEpi<'info> {
+    pub dao: Account<'info, Dao>,
+    #[account(mut, has_one = dao)]
+    pub proposal: Account<'info, Proposal>,
+    pub scorer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct QueueProposal<'info> {
+    pub dao: Account<'info, Dao>,
+    #[account(mut, has_one = dao)]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct VetoProposal<'info> {
+    pub dao: Account<'info, Dao>,
+    #[account(mut, has_one = dao)]
+    pub proposal: Account<'info, Proposal>,
+    pub guardian: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetRiskTier<'info> {
+    pub dao: Account<'info, Dao>,
+    #[account(mut, has_one = dao)]
+    pub proposal: Account<'info, Proposal>,
+    /// A guardian, or the EPI scorer when raising the tier
+    pub assessor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SignOffProposal<'info> {
+    pub dao: Account<'info, Dao>,
+    #[account(mut, has_one = dao)]
+    pub proposal: Account<'info, Proposal>,
+    pub guardian: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(program_id: Pubkey, accounts: Vec<ProposalAccountMeta>, data: Vec<u8>, option: Option<u8>)]
+pub struct InsertInstruction<'info> {
+    #[account(mut, has_one = proposer)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + 32 + 2 + 32 + 4 + accounts.len() * (32 + 1 + 1) + 4 + data.len() + 1 + 1 + 2,
+        seeds = [
+            PROPOSAL_INSTRUCTION_SEED,
+            proposal.key().as_ref(),
+            &proposal.instruction_count.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub proposal_instruction: Account<'info, ProposalInstruction>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteInstruction<'info> {
+    pub dao: Account<'info, Dao>,
+    #[account(mut, has_one = dao)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(mut, has_one = proposal)]
+    pub proposal_instruction: Account<'info, ProposalInstruction>,
+    #[account(mut, seeds = [TREASURY_SEED, dao.key().as_ref()], bump)]
+    pub treasury: SystemAccount<'info>,
+    // remaining_accounts: the target program and every account in the stored metas
+}
+
+#[derive(Accounts)]
+pub struct CreateTreasuryTokenAccount<'info> {
+    pub dao: Account<'info, Dao>,
+    #[account(seeds = [TREASURY_SEED, dao.key().as_ref()], bump)]
+    pub treasury: SystemAccount<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    pub dao: Account<'info, Dao>,
+    #[account(mut, seeds = [TREASURY_SEED, dao.key().as_ref()], bump)]
+    pub treasury: SystemAccount<'info>,
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[account]
+pub struct Dao {
+    /// `Pubkey::default()` once renounced
+    pub authority: Pubkey,
+    pub proposal_count: u64,
+    pub member_count: u64,
+    // Wyoming DAO LLC Compliance Fields
     pub legal_name: String,
     pub registered_agent_address: String,
     pub principal_place_of_business: String,
     pub formation_date: i64,
     pub jurisdiction: String,
     pub entity_type: String,
+    pub membership_registry: Pubkey,
+    pub config: GovernanceConfig,
+    /// Keys allowed to veto proposals during voting or the timelock
+    pub guardians: Vec<Pubkey>,
+    /// Nominated by the authority; becomes the authority once it accepts
+    pub pending_authority: Option<Pubkey>,
+}
+
+pub fn validate_guardians(guardians: &[Pubkey]) -> Result<()> {
+    require!(guardians.len() <= MAX_GUARDIANS, ErrorCode::InvalidGuardianSet);
+    for (i, guardian) in guardians.iter().enumerate() {
+        require!(!guardians[..i].contains(guardian), ErrorCode::InvalidGuardianSet);
+    }
+    Ok(())
+}
+
+/// Every proposal starts in the Critical tier, so a tier that needs guardian
+/// sign-off with no guardians to give it would leave nothing able to pass.
+pub fn validate_guardian_signoff(config: &GovernanceConfig, guardians: &[Pubkey]) -> Result<()> {
+    require!(
+        !guardians.is_empty() || config.risk_tiers.iter().all(|tier| !tier.requires_guardian_signoff),
+        ErrorCode::MissingGuardians
+    );
+    Ok(())
+}
+
+/// Voting rules applied at finalize time. All ratios are in basis points.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct GovernanceConfig {
+    /// Share of total voting power that must cast a vote
+    pub quorum_bps: u16,
+    /// Share of cast votes that must be in favour for ordinary proposals
+    pub approval_threshold_bps: u16,
+    /// Share of cast votes that must be in favour for governance changes
+    pub supermajority_bps: u16,
+    /// Weight of each chamber, indexed by `chamber_index`
+    pub chamber_weights_bps: [u16; CHAMBER_COUNT],
+    pub passage_mode: PassageMode,
+    /// Seconds a queued proposal must wait before it can be executed
+    pub timelock_delay: i64,
+    /// Escrowed at proposal creation; zero disables deposits
+    pub proposal_deposit: u64,
+    /// Token the deposit is taken in, or `None` for lamports
+    pub deposit_mint: Option<Pubkey>,
+    /// Key allowed to attest EPI scores on proposals
+    pub epi_scorer: Option<Pubkey>,
+    /// Minimum attested EPI (scaled by `EPI_SCALE`) to execute; zero disables the check
+    pub epi_floor: u32,
+    /// Indexed by `RiskTier`, each at least as strict as the one before
+    pub risk_tiers: [TierRules; RISK_TIER_COUNT],
+}
+
+/// Mirrors `RiskTier` in `src/policy_engine/risk_classifier.py`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum RiskTier {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+/// Extra requirements for proposals in one risk tier.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub struct TierRules {
+    /// Minimum seconds between voting start and end
+    pub min_voting_period: i64,
+    pub quorum_bps: u16,
+    /// Pass with `supermajority_bps` rather than the approval threshold
+    pub requires_supermajority: bool,
+    /// A guardian must sign off before the proposal can be queued
+    pub requires_guardian_signoff: bool,
+}
+
+/// How a member's voting power turns into tally weight.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum VotingStrategy {
+    Linear,
+    /// Weight is the square root of voting power. Spending a credit budget
+    /// per proposal, the other common form of quadratic voting, is not supported.
+    Quadratic,
+}
+
+impl VotingStrategy {
+    pub fn weight(&self, voting_power: u64) -> u64 {
+        match self {
+            VotingStrategy::Linear => voting_power,
+            // sqrt(u64::MAX * QUADRATIC_SCALE) fits comfortably in a u64
+            VotingStrategy::Quadratic => isqrt(voting_power as u128 * QUADRATIC_SCALE) as u64,
+        }
+    }
+}
+
+/// How the per-chamber results combine into a pass/fail decision.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum PassageMode {
+    /// Each chamber's approval scaled by its weight must clear the threshold
+    WeightedMajority,
+    /// At least `required` chambers must clear the threshold on their own
+    ChamberMajority { required: u8 },
+}
+
+impl GovernanceConfig {
+    pub fn tier(&self, risk_tier: RiskTier) -> &TierRules {
+        &self.risk_tiers[risk_tier as usize]
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let max = BPS_DENOMINATOR as u16;
+        require!(
+            self.quorum_bps <= max
+                && self.approval_threshold_bps <= max
+                && self.supermajority_bps <= max,
+            ErrorCode::InvalidGovernanceConfig
+        );
+        require!(
+            self.supermajority_bps >= self.approval_threshold_bps,
+            ErrorCode::InvalidGovernanceConfig
+        );
+        require!(self.timelock_delay >= 0, ErrorCode::InvalidGovernanceConfig);
+        require!(self.epi_floor <= EPI_SCALE, ErrorCode::InvalidGovernanceConfig);
+        for tier in self.risk_tiers.iter() {
+            require!(
+                tier.min_voting_period >= 0 && tier.quorum_bps <= max,
+                ErrorCode::InvalidGovernanceConfig
+            );
+        }
+        // Critical, where every proposal starts, must be the strictest
+        for pair in self.risk_tiers.windows(2) {
+            let (lower, higher) = (&pair[0], &pair[1]);
+            require!(
+                higher.min_voting_period >= lower.min_voting_period
+                    && higher.quorum_bps >= lower.quorum_bps
+                    && higher.requires_supermajority >= lower.requires_supermajority
+                    && higher.requires_guardian_signoff >= lower.requires_guardian_signoff,
+                ErrorCode::InvalidGovernanceConfig
+            );
+        }
+        let total_weight: u64 = self.chamber_weights_bps.iter().map(|w| *w as u64).sum();
+        require!(total_weight == BPS_DENOMINATOR, ErrorCode::InvalidGovernanceConfig);
+        if let PassageMode::ChamberMajority { required } = self.passage_mode {
+            require!(
+                required > 0 && required as usize <= CHAMBER_COUNT,
+                ErrorCode::InvalidGovernanceConfig
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Arguments to `create_proposal`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateProposalArgs {
+    pub title: String,
+    /// Where the full proposal document lives off-chain
+    pub content_uri: String,
+    /// SHA-256 of the document at `content_uri`
+    pub content_hash: [u8; 32],
+    pub amount: u64,
+    pub recipient: Pubkey,
+    pub mint: Option<Pubkey>,
+    pub voting_starts_at: i64,
+    /// May be extended to the kind's voting period and the tier's minimum
+    pub voting_ends_at: i64,
+    pub action: ProposalAction,
+    /// Only honoured for proposals with no action and no payout
+    pub kind: ProposalKind,
 }
 
 #[account]
 pub struct Proposal {
     pub id: u64,
     pub title: String,
-    pub description: String,
+    /// Where the full proposal document lives off-chain
+    pub content_uri: String,
+    /// SHA-256 of the document at `content_uri`
+    pub content_hash: [u8; 32],
     pub amount: u64,
     pub proposer: Pubkey,
     pub votes_for: u64,
     pub votes_against: u64,
     pub status: ProposalStatus,
     pub created_at: i64,
+    pub voting_starts_at: i64,
+    pub voting_ends_at: i64,
+    pub dao: Pubkey,
+    /// Registry voting power snapshotted at creation, used for quorum
+    pub total_voting_power: u64,
+    pub action: ProposalAction,
+    /// Receives `amount` from the treasury on execution
+    pub recipient: Pubkey,
+    /// Token mint to pay out in, or `None` for lamports
+    pub mint: Option<Pubkey>,
+    /// Tallies per chamber, indexed by `chamber_index`
+    pub chamber_tallies: [ChamberTally; CHAMBER_COUNT],
+    pub instruction_count: u16,
+    /// Instructions run in index order; this is the next one due
+    pub instructions_executed: u16,
+    /// Earliest time a queued proposal can be executed
+    pub eta: i64,
+    pub vetoed_by: Pubkey,
+    /// Hash of the guardian's off-chain veto rationale
+    pub veto_reason_hash: [u8; 32],
+    pub votes_abstain: u64,
+    /// Snapshotted from the DAO config at creation
+    pub voting_strategy: VotingStrategy,
+    /// Raw voting power of everyone who voted, including abstentions
+    pub participating_power: u64,
+    /// Zero for a yes/no proposal, otherwise the number of labelled options
+    pub option_count: u8,
+    /// Set at finalize for multi-option proposals that pass
+    pub winning_option: Option<u8>,
+    /// Set at finalize; decides whether the deposit is refunded
+    pub quorum_reached: bool,
+    pub flagged_spam: bool,
+    pub kind: ProposalKind,
+    pub epi_attestation: Option<EpiAttestation>,
+    pub risk_tier: RiskTier,
+    /// Guardian who signed off, for tiers that require it
+    pub signed_off_by: Option<Pubkey>,
+    /// Voting end before the risk tier's minimum period is applied
+    pub untiered_voting_ends_at: i64,
+}
+
+/// EPI inputs recorded on a proposal by the DAO's scorer, with the EPI the
+/// program computed from them. Scores are scaled by `EPI_SCALE`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct EpiAttestation {
+    pub epi_score: u32,
+    pub profit_score: u32,
+    pub ethics_score: u32,
+    /// Severity of each violation, at most `MAX_EPI_VIOLATIONS`
+    pub violations: Vec<u32>,
+    /// Hash of the inputs the off-chain calculator was run on
+    pub inputs_hash: [u8; 32],
+    pub scorer: Pubkey,
+    pub attested_at: i64,
+}
+
+impl Proposal {
+    /// Moves the proposal to `risk_tier`, keeping voting open at least as
+    /// long as that tier's `rules` require.
+    pub fn set_risk_tier(&mut self, risk_tier: RiskTier, rules: &TierRules) {
+        self.risk_tier = risk_tier;
+        self.voting_ends_at = self
+            .untiered_voting_ends_at
+            .max(self.voting_starts_at.saturating_add(rules.min_voting_period));
+    }
+
+    pub fn require_voting_open(&self, now: i64) -> Result<()> {
+        require!(self.status == ProposalStatus::Active, ErrorCode::ProposalNotActive);
+        require!(now >= self.voting_starts_at, ErrorCode::VotingNotStarted);
+        require!(now < self.voting_ends_at, ErrorCode::VotingClosed);
+        Ok(())
+    }
+
+    /// Instructions run with the treasury's signature, so only Governance
+    /// proposals, which finalize with a supermajority, may carry them. On a
+    /// multi-option proposal each must be tied to one of its options; yes/no
+    /// proposals take untagged instructions only.
+    pub fn validate_instruction(&self, option: Option<u8>) -> Result<()> {
+        require!(
+            self.kind == ProposalKind::Governance,
+            ErrorCode::InstructionsNeedGovernanceKind
+        );
+        let valid = match option {
+            Some(option) => option < self.option_count,
+            None => self.option_count == 0,
+        };
+        require!(valid, ErrorCode::InvalidInstructionOption);
+        Ok(())
+    }
+
+    /// Whether a settled proposal's deposit goes back to the proposer: it must
+    /// have reached quorum and not been flagged as spam.
+    pub fn refunds_deposit(&self) -> bool {
+        !self.flagged_spam && self.quorum_reached
+    }
+
+    pub fn require_executable(&self, now: i64) -> Result<()> {
+        require!(self.status == ProposalStatus::Queued, ErrorCode::ProposalNotQueued);
+        require!(now >= self.eta, ErrorCode::TimelockNotExpired);
+        Ok(())
+    }
+
+    pub fn require_epi_floor(&self, epi_floor: u32) -> Result<()> {
+        if epi_floor == 0 {
+            return Ok(());
+        }
+        let attestation = self
+            .epi_attestation
+            .as_ref()
+            .ok_or(ErrorCode::MissingEpiAttestation)?;
+        require!(attestation.epi_score >= epi_floor, ErrorCode::EpiBelowFloor);
+        Ok(())
+    }
+
+    fn tallies_mut(&mut self, member_type: MemberType, choice: VoteChoice) -> (&mut u64, &mut u64) {
+        let chamber = &mut self.chamber_tallies[chamber_index(member_type)];
+        match choice {
+            VoteChoice::For => (&mut self.votes_for, &mut chamber.votes_for),
+            VoteChoice::Against => (&mut self.votes_against, &mut chamber.votes_against),
+            VoteChoice::Abstain => (&mut self.votes_abstain, &mut chamber.votes_abstain),
+        }
+    }
+
+    /// Adds `voting_power` to quorum and its strategy-adjusted weight to the tallies.
+    pub fn add_vote(&mut self, member_type: MemberType, choice: VoteChoice, voting_power: u64) -> Result<()> {
+        let weight = self.voting_strategy.weight(voting_power);
+        let (total, chamber_total) = self.tallies_mut(member_type, choice);
+        *total = total.checked_add(weight).ok_or(ErrorCode::TallyOverflow)?;
+        *chamber_total = chamber_total
+            .checked_add(weight)
+            .ok_or(ErrorCode::TallyOverflow)?;
+        self.participating_power = self
+            .participating_power
+            .checked_add(voting_power)
+            .ok_or(ErrorCode::TallyOverflow)?;
+        Ok(())
+    }
+
+    pub fn remove_vote(&mut self, member_type: MemberType, choice: VoteChoice, voting_power: u64) -> Result<()> {
+        let weight = self.voting_strategy.weight(voting_power);
+        let (total, chamber_total) = self.tallies_mut(member_type, choice);
+        *total = total.checked_sub(weight).ok_or(ErrorCode::TallyUnderflow)?;
+        *chamber_total = chamber_total
+            .checked_sub(weight)
+            .ok_or(ErrorCode::TallyUnderflow)?;
+        self.participating_power = self
+            .participating_power
+            .checked_sub(voting_power)
+            .ok_or(ErrorCode::TallyUnderflow)?;
+        Ok(())
+    }
+
+    /// Approval in basis points with each chamber normalized to its weight.
+    /// A chamber where nobody voted for or against keeps its weight and counts
+    /// as zero approval, so no class can carry a proposal alone.
+    pub fn weighted_approval_bps(&self, weights_bps: &[u16; CHAMBER_COUNT]) -> u64 {
+        let mut weighted: u128 = 0;
+        for (tally, weight) in self.chamber_tallies.iter().zip(weights_bps) {
+            let cast = tally.decisive_votes();
+            if cast == 0 {
+                continue;
+            }
+            weighted += *weight as u128 * tally.votes_for as u128 * BPS_DENOMINATOR as u128 / cast;
+        }
+        // Weights sum to `BPS_DENOMINATOR`, checked by `GovernanceConfig::validate`
+        (weighted / BPS_DENOMINATOR as u128) as u64
+    }
+
+    pub fn chamber_approves(&self, chamber: usize, threshold_bps: u16) -> bool {
+        let tally = &self.chamber_tallies[chamber];
+        tally.votes_for as u128 * BPS_DENOMINATOR as u128
+            > tally.decisive_votes() * threshold_bps as u128
+    }
+
+    pub fn chambers_approving(&self, threshold_bps: u16) -> u8 {
+        (0..CHAMBER_COUNT)
+            .filter(|chamber| self.chamber_approves(*chamber, threshold_bps))
+            .count() as u8
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq)]
+pub struct ChamberTally {
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub votes_abstain: u64,
+}
+
+impl ChamberTally {
+    /// For plus against; abstentions never affect the approval ratio.
+    pub fn decisive_votes(&self) -> u128 {
+        self.votes_for as u128 + self.votes_against as u128
+    }
+}
+
+/// An instruction the treasury PDA invokes once the proposal has passed.
+#[account]
+pub struct ProposalInstruction {
+    pub proposal: Pubkey,
+    pub index: u16,
+    pub program_id: Pubkey,
+    pub accounts: Vec<ProposalAccountMeta>,
+    pub data: Vec<u8>,
+    pub executed: bool,
+    pub bump: u8,
+    /// Only run if this option wins; `None` runs whatever the outcome
+    pub option: Option<u8>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProposalAccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
 }
 
 #[account]
 pub struct VoteRecord {
     pub has_voted: bool,
-    pub support: bool,
+    pub choice: VoteChoice,
     pub voter: Pubkey,
+    pub voting_power: u64,
+    pub proposal: Pubkey,
+    pub bump: u8,
+    pub member_type: MemberType,
+    /// The voter, or the delegate who voted on their behalf
+    pub cast_by: Pubkey,
+    /// Option preferences on multi-option proposals, padded with `NO_OPTION`
+    pub ranking: [u8; MAX_OPTIONS],
+}
+
+/// Labels and aggregated ballots for a multi-option proposal.
+#[account]
+pub struct ProposalOptions {
+    pub proposal: Pubkey,
+    pub method: TallyMethod,
+    pub labels: Vec<String>,
+    /// Strategy-weighted ballots, merged by identical ranking so the count
+    /// can run at finalize without reading every vote record
+    pub ballots: Vec<RankedBallot>,
+    pub bump: u8,
+}
+
+impl ProposalOptions {
+    pub fn validate_ranking(&self, ranking: &[u8]) -> Result<[u8; MAX_OPTIONS]> {
+        let max_len = match self.method {
+            TallyMethod::Plurality => 1,
+            TallyMethod::RankedChoice => self.labels.len(),
+        };
+        require!(
+            !ranking.is_empty() && ranking.len() <= max_len,
+            ErrorCode::InvalidRanking
+        );
+
+        let mut padded = [NO_OPTION; MAX_OPTIONS];
+        for (i, option) in ranking.iter().enumerate() {
+            require!((*option as usize) < self.labels.len(), ErrorCode::InvalidRanking);
+            require!(!ranking[..i].contains(option), ErrorCode::InvalidRanking);
+            padded[i] = *option;
+        }
+        Ok(padded)
+    }
+
+    pub fn add_ballot(&mut self, ranking: &[u8; MAX_OPTIONS], weight: u64) -> Result<()> {
+        match self.ballots.iter_mut().find(|ballot| ballot.ranking == *ranking) {
+            Some(ballot) => {
+                ballot.weight = ballot.weight.checked_add(weight).ok_or(ErrorCode::TallyOverflow)?;
+            }
+            None => {
+                require!(self.ballots.len() < MAX_BALLOT_KINDS, ErrorCode::InvalidRanking);
+                self.ballots.push(RankedBallot { ranking: *ranking, weight });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn remove_ballot(&mut self, ranking: &[u8; MAX_OPTIONS], weight: u64) -> Result<()> {
+        let ballot = self
+            .ballots
+            .iter_mut()
+            .find(|ballot| ballot.ranking == *ranking)
+            .ok_or(ErrorCode::TallyUnderflow)?;
+        ballot.weight = ballot.weight.checked_sub(weight).ok_or(ErrorCode::TallyUnderflow)?;
+        Ok(())
+    }
+
+    /// Plurality picks the option with the most first preferences. Ranked
+    /// choice repeatedly eliminates the weakest option and transfers its
+    /// ballots until one option holds a majority of the ballots still in play.
+    /// Ties go to the lower option index; elimination ties drop the higher one.
+    pub fn winner(&self) -> Option<u8> {
+        let option_count = self.labels.len();
+        let mut eliminated = [false; MAX_OPTIONS];
+
+        loop {
+            let mut counts = [0u128; MAX_OPTIONS];
+            let mut active_weight: u128 = 0;
+            for ballot in &self.ballots {
+                let preference = ballot
+                    .ranking
+                    .iter()
+                    .take_while(|option| **option != NO_OPTION)
+                    .find(|option| !eliminated[**option as usize]);
+                if let Some(option) = preference {
+                    counts[*option as usize] += ballot.weight as u128;
+                    active_weight += ballot.weight as u128;
+                }
+            }
+            if active_weight == 0 {
+                return None;
+            }
+
+            let remaining = (0..option_count).filter(|i| !eliminated[*i]);
+            let leader = remaining
+                .clone()
+                .fold(None, |best: Option<usize>, i| match best {
+                    Some(b) if counts[b] >= counts[i] => Some(b),
+                    _ => Some(i),
+                })?;
+            if self.method == TallyMethod::Plurality
+                || counts[leader] * 2 > active_weight
+                || remaining.clone().count() == 1
+            {
+                return Some(leader as u8);
+            }
+
+            let weakest = remaining.fold(None, |worst: Option<usize>, i| match worst {
+                Some(w) if counts[w] < counts[i] => Some(w),
+                _ => Some(i),
+            })?;
+            eliminated[weakest] = true;
+        }
+    }
+
+    /// Share of all ballot weight, in basis points, that ranks `option` first.
+    pub fn first_preference_bps(&self, option: u8) -> u64 {
+        let total: u128 = self.ballots.iter().map(|ballot| ballot.weight as u128).sum();
+        if total == 0 {
+            return 0;
+        }
+        let first: u128 = self
+            .ballots
+            .iter()
+            .filter(|ballot| ballot.ranking[0] == option)
+            .map(|ballot| ballot.weight as u128)
+            .sum();
+        (first * BPS_DENOMINATOR as u128 / total) as u64
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RankedBallot {
+    pub ranking: [u8; MAX_OPTIONS],
+    pub weight: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum TallyMethod {
+    Plurality,
+    RankedChoice,
+}
+
+/// Holds a proposer's deposit (lamports directly, or as the authority of a
+/// token account) until the proposal is settled.
+#[account]
+pub struct DepositEscrow {
+    pub proposal: Pubkey,
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub mint: Option<Pubkey>,
+    pub bump: u8,
+}
+
+/// Lends a member's voting power to another member until revoked.
+#[account]
+pub struct Delegation {
+    pub dao: Pubkey,
+    pub delegator: Pubkey,
+    pub delegator_member: Pubkey,
+    pub delegate: Pubkey,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+/// Category of a proposal; selects which `KindRules` apply to it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum ProposalKind {
+    Budget,
+    Security,
+    AiRights,
+    Governance,
+}
+
+/// Rules for one proposal kind, applied on top of the DAO-wide config.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct KindRules {
+    pub quorum_bps: u16,
+    pub approval_threshold_bps: u16,
+    /// Minimum seconds between voting start and close
+    pub voting_period: i64,
+    /// Largest treasury payout, or `None` for no limit
+    pub max_amount: Option<u64>,
+    /// Bitmask of chambers (by `chamber_index`) that must each clear the threshold
+    pub required_chambers: u8,
+    /// Strategy proposals of this kind are created with
+    pub voting_strategy: VotingStrategy,
+}
+
+impl KindRules {
+    pub fn validate(&self) -> Result<()> {
+        let max = BPS_DENOMINATOR as u16;
+        require!(
+            self.quorum_bps <= max && self.approval_threshold_bps <= max,
+            ErrorCode::InvalidKindRules
+        );
+        require!(self.voting_period >= 0, ErrorCode::InvalidKindRules);
+        require!(
+            (self.required_chambers as usize) < 1 << CHAMBER_COUNT,
+            ErrorCode::InvalidKindRules
+        );
+        Ok(())
+    }
+}
+
+#[account]
+pub struct ProposalRules {
+    pub dao: Pubkey,
+    /// Indexed by `ProposalKind`
+    pub rules: [KindRules; PROPOSAL_KIND_COUNT],
+    pub bump: u8,
+}
+
+impl ProposalRules {
+    pub fn get(&self, kind: ProposalKind) -> &KindRules {
+        &self.rules[kind as usize]
+    }
+}
+
+/// What a passed proposal does to the DAO when executed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum ProposalAction {
+    None,
+    UpdateConfig(GovernanceConfig),
+    SetGuardians(Vec<Pubkey>),
+    SetKindRules { kind: ProposalKind, rules: KindRules },
+    UpdateCompliance { field: ComplianceField, value: String },
+}
+
+/// Wyoming DAO LLC details that can only change through a proposal.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum ComplianceField {
+    LegalName,
+    RegisteredAgentAddress,
+    PrincipalPlaceOfBusiness,
+}
+
+impl ComplianceField {
+    pub fn max_len(&self) -> usize {
+        match self {
+            ComplianceField::LegalName => 252,
+            ComplianceField::RegisteredAgentAddress
+            | ComplianceField::PrincipalPlaceOfBusiness => MAX_COMPLIANCE_VALUE_LEN,
+        }
+    }
+}
+
+/// One change to a compliance field, kept so filings can cite the proposal
+/// that authorized it.
+#[account]
+pub struct ComplianceChange {
+    pub dao: Pubkey,
+    pub proposal: Pubkey,
+    pub proposal_id: u64,
+    pub field: ComplianceField,
+    pub old_value: String,
+    pub new_value: String,
+    pub effective_at: i64,
+    pub bump: u8,
+}
+
+impl ProposalAction {
+    /// The kind a proposal with this action is governed by. Rule changes are
+    /// always `Governance` and may not pay out; payouts are always `Budget`.
+    /// Only proposals doing neither keep the proposer's `requested` kind.
+    pub fn kind(&self, amount: u64, requested: ProposalKind) -> Result<ProposalKind> {
+        match self {
+            ProposalAction::None if amount > 0 => Ok(ProposalKind::Budget),
+            ProposalAction::None => Ok(requested),
+            _ => {
+                require!(amount == 0, ErrorCode::InvalidAmount);
+                Ok(ProposalKind::Governance)
+            }
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            ProposalAction::None => Ok(()),
+            ProposalAction::UpdateCompliance { field, value } => {
+                require!(
+                    !value.is_empty() && value.len() <= field.max_len(),
+                    ErrorCode::InvalidComplianceValue
+                );
+                Ok(())
+            }
+            ProposalAction::UpdateConfig(config) => config.validate(),
+            ProposalAction::SetGuardians(guardians) => validate_guardians(guardians),
+            ProposalAction::SetKindRules { rules, .. } => rules.validate(),
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum VoteChoice {
+    For,
+    Against,
+    Abstain,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
@@ -140,6 +2280,9 @@ pub enum ProposalStatus {
     Active,
     Executed,
     Rejected,
+    Passed,
+    Queued,
+    Vetoed,
 }
 
 #[error_code]
@@ -148,4 +2291,655 @@ pub enum ErrorCode {
     ProposalNotActive,
     #[msg("Already voted on this proposal")]
     AlreadyVoted,
+    #[msg("Member account does not belong to the voter")]
+    MemberMismatch,
+    #[msg("Member is not active")]
+    MemberInactive,
+    #[msg("Member has no voting power")]
+    NoVotingPower,
+    #[msg("Vote tally overflow")]
+    TallyOverflow,
+    #[msg("Vote tally underflow")]
+    TallyUnderflow,
+    #[msg("Voting window is invalid")]
+    InvalidVotingWindow,
+    #[msg("Voting has not started")]
+    VotingNotStarted,
+    #[msg("Voting has closed")]
+    VotingClosed,
+    #[msg("Voting is still open")]
+    VotingStillOpen,
+    #[msg("Governance config is invalid")]
+    InvalidGovernanceConfig,
+    #[msg("Proposal has not passed")]
+    ProposalNotPassed,
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    #[msg("Treasury has insufficient funds")]
+    InsufficientTreasuryFunds,
+    #[msg("Token accounts are required for this proposal")]
+    MissingTokenAccounts,
+    #[msg("Token mint does not match the proposal")]
+    MintMismatch,
+    #[msg("Token account is not owned by the treasury")]
+    InvalidTreasuryTokenAccount,
+    #[msg("Token account is not owned by the proposal recipient")]
+    RecipientMismatch,
+    #[msg("Voting has already started")]
+    VotingAlreadyStarted,
+    #[msg("Too many instructions on this proposal")]
+    TooManyInstructions,
+    #[msg("Instruction has already been executed")]
+    InstructionAlreadyExecuted,
+    #[msg("Instructions must be executed in order")]
+    InstructionOutOfOrder,
+    #[msg("Proposal instructions have not all been executed")]
+    InstructionsPending,
+    #[msg("Proposal is not queued")]
+    ProposalNotQueued,
+    #[msg("Timelock has not expired")]
+    TimelockNotExpired,
+    #[msg("Guardian set is invalid")]
+    InvalidGuardianSet,
+    #[msg("Signer is not a guardian of this DAO")]
+    NotGuardian,
+    #[msg("Proposal can no longer be vetoed")]
+    ProposalNotVetoable,
+    #[msg("Delegation accounts are invalid")]
+    InvalidDelegationAccounts,
+    #[msg("Signer is not the delegate")]
+    NotDelegate,
+    #[msg("Members cannot delegate to themselves")]
+    SelfDelegation,
+    #[msg("Proposal options are invalid")]
+    InvalidProposalOptions,
+    #[msg("Proposal options account is required")]
+    MissingProposalOptions,
+    #[msg("This instruction only applies to yes/no proposals")]
+    NotYesNoProposal,
+    #[msg("Ranking is invalid for this proposal")]
+    InvalidRanking,
+    #[msg("Token account is not owned by the deposit escrow")]
+    InvalidEscrowTokenAccount,
+    #[msg("Proposal has not been finalized")]
+    ProposalNotFinalized,
+    #[msg("Invalid proposal kind rules")]
+    InvalidKindRules,
+    #[msg("Amount exceeds the limit for this proposal kind")]
+    AmountExceedsKindLimit,
+    #[msg("Signer is not the pending authority")]
+    NotPendingAuthority,
+    #[msg("Compliance value is empty or too long")]
+    InvalidComplianceValue,
+    #[msg("Title is empty or too long")]
+    InvalidTitle,
+    #[msg("Content URI is empty or too long")]
+    InvalidContentUri,
+    #[msg("Signer is not the DAO's EPI scorer")]
+    NotEpiScorer,
+    #[msg("Proposal is already closed")]
+    ProposalClosed,
+    #[msg("EPI scores must be between 0 and EPI_SCALE")]
+    InvalidEpiScore,
+    #[msg("Proposal has no EPI attestation")]
+    MissingEpiAttestation,
+    #[msg("Proposal EPI is below the DAO floor")]
+    EpiBelowFloor,
+    #[msg("Proposal's risk tier requires guardian sign-off")]
+    MissingGuardianSignoff,
+    #[msg("Member belongs to a different membership registry")]
+    RegistryMismatch,
+    #[msg("Multi-option proposals can't carry an action or payout amount")]
+    InvalidOptionProposal,
+    #[msg("Compliance updates need a compliance change account and payer")]
+    MissingComplianceChange,
+    #[msg("Only a guardian or the EPI scorer can set the risk tier")]
+    NotRiskAssessor,
+    #[msg("Instruction option must name one of the proposal's options")]
+    InvalidInstructionOption,
+    #[msg("Only Governance proposals can carry instructions")]
+    InstructionsNeedGovernanceKind,
+    #[msg("A risk tier requires guardian sign-off but there are no guardians")]
+    MissingGuardians,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: i64 = 86_400;
+
+    fn tier(
+        min_voting_period: i64,
+        quorum_bps: u16,
+        requires_supermajority: bool,
+        requires_guardian_signoff: bool,
+    ) -> TierRules {
+        TierRules {
+            min_voting_period,
+            quorum_bps,
+            requires_supermajority,
+            requires_guardian_signoff,
+        }
+    }
+
+    fn config() -> GovernanceConfig {
+        GovernanceConfig {
+            quorum_bps: 4_000,
+            approval_threshold_bps: 5_000,
+            supermajority_bps: 6_667,
+            chamber_weights_bps: [3_334, 3_333, 3_333],
+            passage_mode: PassageMode::WeightedMajority,
+            timelock_delay: 2 * DAY,
+            proposal_deposit: 0,
+            deposit_mint: None,
+            epi_scorer: None,
+            epi_floor: 0,
+            risk_tiers: [
+                tier(0, 0, false, false),
+                tier(3 * DAY, 2_000, false, false),
+                tier(7 * DAY, 4_000, true, false),
+                tier(14 * DAY, 6_000, true, true),
+            ],
+        }
+    }
+
+    fn proposal() -> Proposal {
+        Proposal {
+            id: 0,
+            title: String::new(),
+            content_uri: String::new(),
+            content_hash: [0; 32],
+            amount: 0,
+            proposer: Pubkey::default(),
+            votes_for: 0,
+            votes_against: 0,
+            status: ProposalStatus::Active,
+            created_at: 0,
+            voting_starts_at: 0,
+            voting_ends_at: DAY,
+            dao: Pubkey::default(),
+            total_voting_power: 0,
+            action: ProposalAction::None,
+            recipient: Pubkey::default(),
+            mint: None,
+            chamber_tallies: [ChamberTally::default(); CHAMBER_COUNT],
+            instruction_count: 0,
+            instructions_executed: 0,
+            eta: 0,
+            vetoed_by: Pubkey::default(),
+            veto_reason_hash: [0; 32],
+            votes_abstain: 0,
+            voting_strategy: VotingStrategy::Linear,
+            participating_power: 0,
+            option_count: 0,
+            winning_option: None,
+            quorum_reached: false,
+            flagged_spam: false,
+            kind: ProposalKind::Budget,
+            epi_attestation: None,
+            risk_tier: RiskTier::Critical,
+            signed_off_by: None,
+            untiered_voting_ends_at: DAY,
+        }
+    }
+
+    fn tally(votes_for: u64, votes_against: u64, votes_abstain: u64) -> ChamberTally {
+        ChamberTally {
+            votes_for,
+            votes_against,
+            votes_abstain,
+        }
+    }
+
+    #[test]
+    fn config_accepts_sane_values() {
+        assert!(config().validate().is_ok());
+    }
+
+    #[test]
+    fn config_rejects_ratios_above_one() {
+        let mut c = config();
+        c.quorum_bps = 10_001;
+        assert!(c.validate().is_err());
+
+        let mut c = config();
+        c.approval_threshold_bps = 10_001;
+        c.supermajority_bps = 10_001;
+        assert!(c.validate().is_err());
+
+        let mut c = config();
+        c.risk_tiers[3].quorum_bps = 10_001;
+        assert!(c.validate().is_err());
+    }
+
+    #[test]
+    fn config_rejects_supermajority_below_approval() {
+        let mut c = config();
+        c.supermajority_bps = 4_999;
+        assert!(c.validate().is_err());
+
+        c.supermajority_bps = 5_000;
+        assert!(c.validate().is_ok());
+    }
+
+    #[test]
+    fn config_rejects_negative_durations() {
+        let mut c = config();
+        c.timelock_delay = -1;
+        assert!(c.validate().is_err());
+
+        let mut c = config();
+        c.risk_tiers[0].min_voting_period = -1;
+        assert!(c.validate().is_err());
+    }
+
+    #[test]
+    fn config_rejects_epi_floor_above_scale() {
+        let mut c = config();
+        c.epi_floor = EPI_SCALE;
+        assert!(c.validate().is_ok());
+
+        c.epi_floor = EPI_SCALE + 1;
+        assert!(c.validate().is_err());
+    }
+
+    #[test]
+    fn config_requires_chamber_weights_to_sum_to_one() {
+        let mut c = config();
+        c.chamber_weights_bps = [3_333, 3_333, 3_333];
+        assert!(c.validate().is_err());
+
+        c.chamber_weights_bps = [10_000, 0, 0];
+        assert!(c.validate().is_ok());
+    }
+
+    #[test]
+    fn config_bounds_chamber_majority() {
+        let mut c = config();
+        for (required, valid) in [(0, false), (1, true), (3, true), (4, false)] {
+            c.passage_mode = PassageMode::ChamberMajority { required };
+            assert_eq!(c.validate().is_ok(), valid, "required = {required}");
+        }
+    }
+
+    #[test]
+    fn config_requires_tiers_in_increasing_strictness() {
+        // Equal neighbours are allowed
+        let mut c = config();
+        c.risk_tiers[1] = c.risk_tiers[0];
+        assert!(c.validate().is_ok());
+
+        let mut c = config();
+        c.risk_tiers[2].min_voting_period = 2 * DAY;
+        assert!(c.validate().is_err());
+
+        let mut c = config();
+        c.risk_tiers[3].quorum_bps = 3_000;
+        assert!(c.validate().is_err());
+
+        let mut c = config();
+        c.risk_tiers[3].requires_supermajority = false;
+        assert!(c.validate().is_err());
+
+        let mut c = config();
+        c.risk_tiers[1].requires_guardian_signoff = true;
+        assert!(c.validate().is_err());
+    }
+
+    #[test]
+    fn guardian_signoff_needs_guardians() {
+        let mut c = config();
+        assert!(validate_guardian_signoff(&c, &[]).is_err());
+        assert!(validate_guardian_signoff(&c, &[Pubkey::new_unique()]).is_ok());
+
+        c.risk_tiers[3].requires_guardian_signoff = false;
+        assert!(validate_guardian_signoff(&c, &[]).is_ok());
+    }
+
+    #[test]
+    fn weighted_approval_normalizes_each_chamber_to_its_weight() {
+        let mut p = proposal();
+        p.chamber_tallies = [tally(100, 0, 0), tally(1, 3, 0), tally(0, 10, 0)];
+        // 100% × 0.5 + 25% × 0.25 + 0% × 0.25
+        assert_eq!(p.weighted_approval_bps(&[5_000, 2_500, 2_500]), 5_625);
+    }
+
+    #[test]
+    fn weighted_approval_counts_silent_chambers_as_zero() {
+        let mut p = proposal();
+        // Abstentions alone count as silence too
+        p.chamber_tallies = [tally(3, 1, 0), tally(0, 0, 0), tally(0, 0, 50)];
+        assert_eq!(p.weighted_approval_bps(&[3_334, 3_333, 3_333]), 2_500);
+
+        p.chamber_tallies = [tally(10, 0, 0), tally(0, 0, 0), tally(0, 0, 0)];
+        assert_eq!(p.weighted_approval_bps(&[10_000, 0, 0]), 10_000);
+
+        p.chamber_tallies = [ChamberTally::default(); CHAMBER_COUNT];
+        assert_eq!(p.weighted_approval_bps(&[3_334, 3_333, 3_333]), 0);
+    }
+
+    #[test]
+    fn chamber_approves_strictly_above_threshold() {
+        let mut p = proposal();
+        p.chamber_tallies = [tally(50, 50, 0), tally(51, 49, 100), tally(0, 0, 10)];
+        assert!(!p.chamber_approves(0, 5_000));
+        assert!(p.chamber_approves(1, 5_000));
+        assert!(!p.chamber_approves(2, 0));
+        assert!(p.chamber_approves(0, 4_999));
+        assert_eq!(p.chambers_approving(5_000), 1);
+        assert_eq!(p.chambers_approving(4_999), 2);
+    }
+
+    fn vote_record(proposal: Pubkey, voter: Pubkey, cast_by: Pubkey) -> VoteRecord {
+        VoteRecord {
+            has_voted: true,
+            choice: VoteChoice::For,
+            voter,
+            voting_power: 10,
+            proposal,
+            bump: 255,
+            member_type: MemberType::Organization,
+            cast_by,
+            ranking: [NO_OPTION; MAX_OPTIONS],
+        }
+    }
+
+    fn account_info(key: Pubkey, record: &VoteRecord) -> AccountInfo<'static> {
+        let mut data = Vec::new();
+        record.try_serialize(&mut data).unwrap();
+        AccountInfo::new(
+            Box::leak(Box::new(key)),
+            false,
+            true,
+            Box::leak(Box::new(1_000_000)),
+            Box::leak(data.into_boxed_slice()),
+            &ID,
+            false,
+            0,
+        )
+    }
+
+    fn accounts(infos: Vec<AccountInfo<'static>>) -> &'static [AccountInfo<'static>] {
+        Box::leak(infos.into_boxed_slice())
+    }
+
+    #[test]
+    fn delegated_records_follow_only_their_delegate() {
+        let proposal = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        let delegator = Pubkey::new_unique();
+        let cast = account_info(
+            Pubkey::new_unique(),
+            &vote_record(proposal, delegator, delegate),
+        );
+
+        let records = delegated_vote_records(&proposal, &delegate, accounts(vec![cast.clone()])).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].voter, delegator);
+
+        // Passing the same record twice would move its weight twice
+        assert!(delegated_vote_records(&proposal, &delegate, accounts(vec![cast.clone(), cast])).is_err());
+
+        // The delegator has since voted directly
+        let overridden = account_info(
+            Pubkey::new_unique(),
+            &vote_record(proposal, delegator, delegator),
+        );
+        assert!(delegated_vote_records(&proposal, &delegate, accounts(vec![overridden])).is_err());
+
+        // The delegate's own record is handled by the instruction itself
+        let own = account_info(Pubkey::new_unique(), &vote_record(proposal, delegate, delegate));
+        assert!(delegated_vote_records(&proposal, &delegate, accounts(vec![own])).is_err());
+
+        let elsewhere = account_info(
+            Pubkey::new_unique(),
+            &vote_record(Pubkey::new_unique(), delegator, delegate),
+        );
+        assert!(delegated_vote_records(&proposal, &delegate, accounts(vec![elsewhere])).is_err());
+    }
+
+    #[test]
+    fn isqrt_floors_exactly() {
+        for n in 0..10_000u128 {
+            let root = isqrt(n);
+            assert!(root * root <= n && (root + 1) * (root + 1) > n, "isqrt({n}) = {root}");
+        }
+        assert_eq!(isqrt(u64::MAX as u128 * QUADRATIC_SCALE), 4_294_967_295_999);
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+    }
+
+    #[test]
+    fn quadratic_weight_keeps_three_decimals() {
+        assert_eq!(VotingStrategy::Linear.weight(400), 400);
+        assert_eq!(VotingStrategy::Quadratic.weight(400), 20_000);
+        assert_eq!(VotingStrategy::Quadratic.weight(2), 1_414);
+        assert_eq!(VotingStrategy::Quadratic.weight(0), 0);
+    }
+
+    fn ranking(options: &[u8]) -> [u8; MAX_OPTIONS] {
+        let mut padded = [NO_OPTION; MAX_OPTIONS];
+        padded[..options.len()].copy_from_slice(options);
+        padded
+    }
+
+    fn options(method: TallyMethod, option_count: usize, ballots: &[(&[u8], u64)]) -> ProposalOptions {
+        ProposalOptions {
+            proposal: Pubkey::default(),
+            method,
+            labels: (0..option_count).map(|i| format!("Option {i}")).collect(),
+            ballots: ballots
+                .iter()
+                .map(|(options, weight)| RankedBallot {
+                    ranking: ranking(options),
+                    weight: *weight,
+                })
+                .collect(),
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn plurality_counts_first_preferences_only() {
+        let o = options(
+            TallyMethod::Plurality,
+            3,
+            &[(&[0], 40), (&[1], 35), (&[2], 25)],
+        );
+        assert_eq!(o.winner(), Some(0));
+
+        // Ties go to the lower index
+        let o = options(TallyMethod::Plurality, 3, &[(&[2], 30), (&[1], 30)]);
+        assert_eq!(o.winner(), Some(1));
+
+        assert_eq!(options(TallyMethod::Plurality, 3, &[]).winner(), None);
+    }
+
+    #[test]
+    fn ranked_choice_transfers_eliminated_ballots() {
+        let o = options(
+            TallyMethod::RankedChoice,
+            3,
+            &[(&[0], 40), (&[1, 0], 35), (&[2, 1], 25)],
+        );
+        // 2 is eliminated and its ballots move to 1, which then holds 60 of 100
+        assert_eq!(o.winner(), Some(1));
+    }
+
+    #[test]
+    fn ranked_choice_majority_ignores_exhausted_ballots() {
+        let o = options(
+            TallyMethod::RankedChoice,
+            3,
+            &[(&[0], 40), (&[1], 35), (&[2], 25)],
+        );
+        // Once 2 is out its ballots are exhausted, and 40 of the 75 still in
+        // play is a majority
+        assert_eq!(o.winner(), Some(0));
+    }
+
+    #[test]
+    fn ranked_choice_elimination_ties_drop_the_higher_index() {
+        let o = options(
+            TallyMethod::RankedChoice,
+            4,
+            &[(&[0], 40), (&[1, 2], 30), (&[2, 1], 15), (&[3, 2], 15)],
+        );
+        // 2 and 3 tie for last, so 3 goes and hands 2 its ballots. Then 1 and
+        // 2 tie at 30, 2 goes, and 1 holds 45 of the 85 still in play
+        assert_eq!(o.winner(), Some(1));
+    }
+
+    #[test]
+    fn ranked_choice_with_every_ballot_exhausted_has_no_winner() {
+        let o = options(TallyMethod::RankedChoice, 2, &[(&[0], 0)]);
+        assert_eq!(o.winner(), None);
+    }
+
+    #[test]
+    fn validate_ranking_pads_and_rejects_bad_rankings() {
+        let ranked = options(TallyMethod::RankedChoice, 3, &[]);
+        assert_eq!(ranked.validate_ranking(&[2, 0]).unwrap(), ranking(&[2, 0]));
+        assert!(ranked.validate_ranking(&[2, 0, 1]).is_ok());
+        assert!(ranked.validate_ranking(&[]).is_err());
+        assert!(ranked.validate_ranking(&[3]).is_err());
+        assert!(ranked.validate_ranking(&[1, 1]).is_err());
+        assert!(ranked.validate_ranking(&[0, 1, 2, 0]).is_err());
+
+        let plurality = options(TallyMethod::Plurality, 3, &[]);
+        assert!(plurality.validate_ranking(&[1]).is_ok());
+        assert!(plurality.validate_ranking(&[1, 0]).is_err());
+    }
+
+    #[test]
+    fn ballots_merge_by_ranking() {
+        let mut o = options(TallyMethod::RankedChoice, 3, &[]);
+        o.add_ballot(&ranking(&[0, 1]), 10).unwrap();
+        o.add_ballot(&ranking(&[0, 1]), 5).unwrap();
+        o.add_ballot(&ranking(&[0]), 7).unwrap();
+        assert_eq!(o.ballots.len(), 2);
+        assert_eq!(o.ballots[0].weight, 15);
+
+        o.remove_ballot(&ranking(&[0, 1]), 10).unwrap();
+        assert_eq!(o.ballots[0].weight, 5);
+        assert!(o.remove_ballot(&ranking(&[0]), 8).is_err());
+        assert!(o.remove_ballot(&ranking(&[2]), 1).is_err());
+        assert!(o.add_ballot(&ranking(&[0]), u64::MAX).is_err());
+    }
+
+    #[test]
+    fn ballot_kinds_are_bounded() {
+        let mut o = options(TallyMethod::RankedChoice, MAX_OPTIONS, &[]);
+        // Distinct placeholder rankings fill every slot
+        o.ballots = (0..MAX_BALLOT_KINDS)
+            .map(|i| RankedBallot {
+                ranking: [NO_OPTION - 1 - i as u8; MAX_OPTIONS],
+                weight: 1,
+            })
+            .collect();
+        assert!(o.add_ballot(&ranking(&[0, 1]), 1).is_err());
+    }
+
+    #[test]
+    fn first_preference_share_counts_exhausted_ballots() {
+        let o = options(
+            TallyMethod::RankedChoice,
+            3,
+            &[(&[0], 40), (&[1, 0], 35), (&[2], 25)],
+        );
+        // Later preferences and exhausted ballots don't count toward the share,
+        // but every ballot counts toward the total
+        assert_eq!(o.first_preference_bps(0), 4_000);
+        assert_eq!(o.first_preference_bps(1), 3_500);
+        assert_eq!(options(TallyMethod::Plurality, 2, &[]).first_preference_bps(0), 0);
+    }
+
+    #[test]
+    fn instructions_need_governance_kind_and_matching_options() {
+        let mut p = proposal();
+        assert!(p.validate_instruction(None).is_err());
+
+        p.kind = ProposalKind::Governance;
+        assert!(p.validate_instruction(None).is_ok());
+        assert!(p.validate_instruction(Some(0)).is_err());
+
+        p.option_count = 3;
+        assert!(p.validate_instruction(Some(2)).is_ok());
+        assert!(p.validate_instruction(Some(3)).is_err());
+        assert!(p.validate_instruction(None).is_err());
+    }
+
+    #[test]
+    fn deposit_refunded_on_quorum_unless_spam() {
+        let cases = [
+            (ProposalStatus::Passed, true, false, true),
+            (ProposalStatus::Rejected, true, false, true),
+            (ProposalStatus::Rejected, false, false, false),
+            (ProposalStatus::Vetoed, false, false, false),
+            (ProposalStatus::Vetoed, false, true, false),
+            (ProposalStatus::Vetoed, true, true, false),
+        ];
+        for (status, quorum_reached, flagged_spam, refunded) in cases {
+            let mut p = proposal();
+            p.status = status;
+            p.quorum_reached = quorum_reached;
+            p.flagged_spam = flagged_spam;
+            assert_eq!(
+                p.refunds_deposit(),
+                refunded,
+                "quorum_reached = {quorum_reached}, flagged_spam = {flagged_spam}"
+            );
+        }
+    }
+
+    #[test]
+    fn kind_follows_the_action_and_amount() {
+        let guardians = ProposalAction::SetGuardians(Vec::new());
+        for requested in [ProposalKind::Budget, ProposalKind::Security] {
+            assert!(guardians.kind(0, requested).unwrap() == ProposalKind::Governance);
+            assert!(ProposalAction::None.kind(1, requested).unwrap() == ProposalKind::Budget);
+        }
+        assert!(guardians.kind(1, ProposalKind::Governance).is_err());
+        assert!(
+            ProposalAction::None.kind(0, ProposalKind::AiRights).unwrap() == ProposalKind::AiRights
+        );
+
+        let compliance = ProposalAction::UpdateCompliance {
+            field: ComplianceField::LegalName,
+            value: "ExecAI DAO LLC".to_string(),
+        };
+        assert!(compliance.kind(0, ProposalKind::Security).unwrap() == ProposalKind::Governance);
+    }
+
+    #[test]
+    fn voting_window_is_half_open() {
+        let mut p = proposal();
+        p.voting_starts_at = DAY;
+        p.voting_ends_at = 2 * DAY;
+        assert!(p.require_voting_open(DAY - 1).is_err());
+        assert!(p.require_voting_open(DAY).is_ok());
+        assert!(p.require_voting_open(2 * DAY - 1).is_ok());
+        assert!(p.require_voting_open(2 * DAY).is_err());
+
+        p.status = ProposalStatus::Vetoed;
+        assert!(p.require_voting_open(DAY).is_err());
+    }
+
+    #[test]
+    fn risk_tier_sets_the_minimum_voting_period() {
+        let c = config();
+        let mut p = proposal();
+        p.set_risk_tier(RiskTier::Critical, c.tier(RiskTier::Critical));
+        assert_eq!(p.voting_ends_at, 14 * DAY);
+
+        // Lowering the tier gives back the shorter window
+        p.set_risk_tier(RiskTier::Medium, c.tier(RiskTier::Medium));
+        assert!(p.risk_tier == RiskTier::Medium);
+        assert_eq!(p.voting_ends_at, 3 * DAY);
+
+        // The proposer's own end time is never cut short
+        p.untiered_voting_ends_at = 5 * DAY;
+        p.set_risk_tier(RiskTier::Low, c.tier(RiskTier::Low));
+        assert_eq!(p.voting_ends_at, 5 * DAY);
+    }
 }
@@ -0,0 +1,62 @@
+//! Checks the fixed-point EPI port against vectors generated from the Python
+//! implementation by `scripts/generate_epi_vectors.py`.
+
+use governance::epi::{self, EPI_TOLERANCE};
+
+const VECTORS: &str = include_str!("epi_vectors.txt");
+
+fn fixed(value: &str) -> u64 {
+    (value.parse::<f64>().unwrap() * 1_000_000.0).round() as u64
+}
+
+fn violations(value: &str) -> Vec<u64> {
+    if value == "-" {
+        return Vec::new();
+    }
+    value.split(',').map(fixed).collect()
+}
+
+fn assert_close(name: &str, line: &str, actual: u64, expected: u64) {
+    assert!(
+        actual.abs_diff(expected) <= EPI_TOLERANCE,
+        "{name}: got {actual}, expected {expected} ({line})"
+    );
+}
+
+fn vectors(kind: &str) -> impl Iterator<Item = (&str, Vec<&str>)> {
+    VECTORS
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .map(|line| (line, line.split_whitespace().collect::<Vec<_>>()))
+        .filter(move |(_, fields)| fields[0] == kind)
+}
+
+#[test]
+fn epi_matches_python() {
+    let mut count = 0;
+    for (line, fields) in vectors("epi") {
+        let result = epi::compute_epi(fixed(fields[1]), fixed(fields[2]), &violations(fields[3]));
+
+        assert_close("harmonic_mean", line, result.harmonic_mean, fixed(fields[4]));
+        assert_close("balance_penalty", line, result.balance_penalty, fixed(fields[5]));
+        assert_close("trust", line, result.trust, fixed(fields[6]));
+        match result.golden_ratio_deviation {
+            Some(deviation) => assert_close("golden_ratio_deviation", line, deviation, fixed(fields[7])),
+            None => assert_eq!(fields[7], "inf", "golden_ratio_deviation ({line})"),
+        }
+        assert_close("epi", line, result.epi, fixed(fields[8]));
+        count += 1;
+    }
+    assert!(count > 0, "no epi vectors found");
+}
+
+#[test]
+fn trust_floor_matches_python() {
+    let mut count = 0;
+    for (line, fields) in vectors("trust") {
+        let trust = epi::trust(&violations(fields[2]), fixed(fields[1]));
+        assert_close("trust", line, trust, fixed(fields[3]));
+        count += 1;
+    }
+    assert!(count > 0, "no trust vectors found");
+}
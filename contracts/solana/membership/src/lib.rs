@@ -10,6 +10,7 @@ pub mod membership {
         let registry = &mut ctx.accounts.registry;
         registry.authority = ctx.accounts.authority.key();
         registry.member_count = 0;
+        registry.total_voting_power = 0;
         Ok(())
     }
 
@@ -24,6 +25,7 @@ pub mod membership {
         let registry = &mut ctx.accounts.registry;
         let member = &mut ctx.accounts.member;
 
+        member.registry = registry.key();
         member.pubkey = ctx.accounts.member_pubkey.key();
         member.member_type = member_type;
         member.voting_power = voting_power;
@@ -36,6 +38,10 @@ pub mod membership {
         member.kyc_verified = false; // Requires separate verification process
 
         registry.member_count += 1;
+        registry.total_voting_power = registry
+            .total_voting_power
+            .checked_add(voting_power)
+            .ok_or(ErrorCode::VotingPowerOverflow)?;
 
         Ok(())
     }
@@ -43,7 +49,7 @@ pub mod membership {
 
 #[derive(Accounts)]
 pub struct Initialize<'info> {
-    #[account(init, payer = authority, space = 8 + 32 + 8)]
+    #[account(init, payer = authority, space = 8 + 32 + 8 + 8)]
     pub registry: Account<'info, MemberRegistry>,
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -52,9 +58,9 @@ pub struct Initialize<'info> {
 
 #[derive(Accounts)]
 pub struct AddMember<'info> {
-    #[account(mut)]
+    #[account(mut, has_one = authority)]
     pub registry: Account<'info, MemberRegistry>,
-    #[account(init, payer = authority, space = 8 + 32 + 1 + 8 + 8 + 1 + 256 + 512 + 64 + 1)]
+    #[account(init, payer = authority, space = 8 + 32 + 32 + 1 + 8 + 8 + 1 + 256 + 512 + 64 + 1)]
     pub member: Account<'info, Member>,
     /// CHECK: Member pubkey is validated by the program logic
     pub member_pubkey: AccountInfo<'info>,
@@ -67,10 +73,13 @@ pub struct AddMember<'info> {
 pub struct MemberRegistry {
     pub authority: Pubkey,
     pub member_count: u64,
+    pub total_voting_power: u64,
 }
 
 #[account]
 pub struct Member {
+    /// Registry the member was added to
+    pub registry: Pubkey,
     pub pubkey: Pubkey,
     pub member_type: MemberType,
     pub voting_power: u64,
@@ -83,9 +92,15 @@ pub struct Member {
     pub kyc_verified: bool,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
 pub enum MemberType {
     Human,
     AI,
     Organization,
 }
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Total voting power overflow")]
+    VotingPowerOverflow,
+}
@@ -0,0 +1,218 @@
+use anchor_lang::prelude::*;
+
+declare_id!("2wdNiKCTS1AKhKLFCZLfFHSdPidAAppWr5ZDMRQSVrpJ");
+
+pub const CONFIG_SEED: &[u8] = b"config";
+pub const DAILY_ROOT_SEED: &[u8] = b"root";
+/// Dates are `YYYY-MM-DD`, matching `DailyMerkleAnchor` in `src/trust_stack/merkle_tree.py`
+pub const DATE_LEN: usize = 10;
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Days since 1970-01-01 for a `YYYY-MM-DD` date, or `None` if it isn't one.
+pub fn parse_date(date: &str) -> Option<i64> {
+    let bytes = date.as_bytes();
+    if bytes.len() != DATE_LEN || bytes[4] != b'-' || bytes[7] != b'-' {
+        return None;
+    }
+    let number = |range: std::ops::Range<usize>| -> Option<i64> {
+        bytes[range].iter().try_fold(0i64, |acc, b| {
+            b.is_ascii_digit().then(|| acc * 10 + (b - b'0') as i64)
+        })
+    };
+    let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return None,
+    };
+    if day < 1 || day > days_in_month {
+        return None;
+    }
+
+    // Howard Hinnant's days_from_civil
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    Some(era * 146_097 + doe - 719_468)
+}
+
+#[program]
+pub mod merkle_anchor {
+    use super::*;
+
+    /// Sets up the config; only the program's upgrade authority may call it.
+    /// `authority` can rotate the logger; set it to the governance treasury
+    /// PDA to put rotation behind a proposal.
+    pub fn initialize(ctx: Context<Initialize>, authority: Pubkey, logger: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.authority = authority;
+        config.logger = logger;
+        config.bump = ctx.bumps.config;
+        Ok(())
+    }
+
+    pub fn set_logger(ctx: Context<SetLogger>, logger: Pubkey) -> Result<()> {
+        ctx.accounts.config.logger = logger;
+        Ok(())
+    }
+
+    /// Anchors the event-log Merkle root for a day. The account can't be
+    /// written again, so each day has exactly one root.
+    pub fn anchor_root(ctx: Context<AnchorRoot>, date: String, merkle_root: [u8; 32]) -> Result<()> {
+        let days = parse_date(&date).ok_or(ErrorCode::InvalidDate)?;
+        let now = Clock::get()?.unix_timestamp;
+        require!(days <= now.div_euclid(SECONDS_PER_DAY), ErrorCode::DateInFuture);
+
+        let daily_root = &mut ctx.accounts.daily_root;
+        daily_root.date = date;
+        daily_root.merkle_root = merkle_root;
+        daily_root.logger = ctx.accounts.logger.key();
+        daily_root.anchored_at = now;
+        daily_root.bump = ctx.bumps.daily_root;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(init, payer = payer, space = 8 + 32 + 32 + 1, seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, AnchorConfig>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, program::MerkleAnchor>,
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(payer.key())
+            @ ErrorCode::NotUpgradeAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetLogger<'info> {
+    #[account(mut, seeds = [CONFIG_SEED], bump = config.bump, has_one = authority)]
+    pub config: Account<'info, AnchorConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(date: String)]
+pub struct AnchorRoot<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = logger)]
+    pub config: Account<'info, AnchorConfig>,
+    // `init` fails if the day already has a root
+    #[account(
+        init,
+        payer = logger,
+        space = 8 + 4 + DATE_LEN + 32 + 32 + 8 + 1,
+        seeds = [DAILY_ROOT_SEED, date.as_bytes()],
+        bump
+    )]
+    pub daily_root: Account<'info, DailyRoot>,
+    #[account(mut)]
+    pub logger: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[account]
+pub struct AnchorConfig {
+    pub authority: Pubkey,
+    /// Only key allowed to anchor roots
+    pub logger: Pubkey,
+    pub bump: u8,
+}
+
+#[account]
+pub struct DailyRoot {
+    pub date: String,
+    pub merkle_root: [u8; 32],
+    pub logger: Pubkey,
+    pub anchored_at: i64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Date must be a valid YYYY-MM-DD")]
+    InvalidDate,
+    #[msg("Cannot anchor a root for a future date")]
+    DateInFuture,
+    #[msg("Only the program's upgrade authority can initialize it")]
+    NotUpgradeAuthority,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_days_since_epoch() {
+        assert_eq!(parse_date("1970-01-01"), Some(0));
+        assert_eq!(parse_date("1969-12-31"), Some(-1));
+        assert_eq!(parse_date("2000-03-01"), Some(11_017));
+        assert_eq!(parse_date("2024-02-29"), Some(19_782));
+        assert_eq!(parse_date("2026-10-17"), Some(20_743));
+    }
+
+    #[test]
+    fn consecutive_dates_are_one_day_apart() {
+        let mut previous = parse_date("1999-12-31").unwrap();
+        let dates = [
+            "2000-01-01",
+            "2000-02-28",
+            "2000-02-29",
+            "2000-03-01",
+            "2000-12-31",
+            "2001-01-01",
+        ];
+        for date in dates {
+            let days = parse_date(date).unwrap();
+            assert!(days > previous, "{date}");
+            previous = days;
+        }
+        assert_eq!(parse_date("2000-03-01").unwrap() - parse_date("2000-02-28").unwrap(), 2);
+        assert_eq!(parse_date("2001-01-01").unwrap() - parse_date("2000-01-01").unwrap(), 366);
+        assert_eq!(parse_date("2101-01-01").unwrap() - parse_date("2100-01-01").unwrap(), 365);
+    }
+
+    #[test]
+    fn checks_days_against_month_and_leap_year() {
+        assert!(parse_date("2024-04-30").is_some());
+        assert_eq!(parse_date("2024-04-31"), None);
+        assert_eq!(parse_date("2024-01-00"), None);
+        assert_eq!(parse_date("2024-01-32"), None);
+        assert_eq!(parse_date("2024-13-01"), None);
+        assert_eq!(parse_date("2024-00-01"), None);
+
+        // Leap years: every fourth, except centuries not divisible by 400
+        assert!(parse_date("2000-02-29").is_some());
+        assert!(parse_date("2024-02-29").is_some());
+        assert_eq!(parse_date("2023-02-29"), None);
+        assert_eq!(parse_date("1900-02-29"), None);
+        assert_eq!(parse_date("2100-02-29"), None);
+    }
+
+    #[test]
+    fn rejects_malformed_dates() {
+        let dates = [
+            "",
+            "2024-1-01",
+            "2024/01/01",
+            "24-01-2024",
+            "2024-01-01T",
+            "2024-0a-01",
+            "+024-01-01",
+        ];
+        for date in dates {
+            assert_eq!(parse_date(date), None, "{date:?}");
+        }
+    }
+}
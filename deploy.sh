@@ -21,6 +21,7 @@ else
     echo "Building with cargo..."
     cd programs/governance && cargo build-bpf --manifest-path Cargo.toml --bpf-out-dir ../../target/deploy
     cd ../membership && cargo build-bpf --manifest-path Cargo.toml --bpf-out-dir ../../target/deploy
+    cd ../merkle_anchor && cargo build-bpf --manifest-path Cargo.toml --bpf-out-dir ../../target/deploy
     cd ../..
 fi
 
@@ -41,6 +42,11 @@ else
         MEMBERSHIP_ID=$(solana program deploy target/deploy/membership.so | grep "Program Id:" | awk '{print $3}')
         echo "Membership Program ID: $MEMBERSHIP_ID"
     fi
+    if [ -f "target/deploy/merkle_anchor.so" ]; then
+        echo "Deploying merkle anchor contract..."
+        MERKLE_ANCHOR_ID=$(solana program deploy target/deploy/merkle_anchor.so | grep "Program Id:" | awk '{print $3}')
+        echo "Merkle Anchor Program ID: $MERKLE_ANCHOR_ID"
+    fi
 fi
 
 echo ""
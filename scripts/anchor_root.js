@@ -0,0 +1,60 @@
+const fs = require('fs');
+const path = require('path');
+const { Connection, PublicKey, Keypair, SystemProgram, Transaction, TransactionInstruction, sendAndConfirmTransaction } = require('@solana/web3.js');
+
+const RPC_URL = process.env.RPC_URL || 'https://api.devnet.solana.com';
+const PROGRAM_ID = new PublicKey(process.env.MERKLE_ANCHOR_PROGRAM_ID || '2wdNiKCTS1AKhKLFCZLfFHSdPidAAppWr5ZDMRQSVrpJ');
+// Must be the logger key registered in the program's config
+const LOGGER_KEYPAIR = process.env.LOGGER_KEYPAIR || path.join(process.env.HOME, '.config/solana/id.json');
+
+// One root per day, keyed by the YYYY-MM-DD date string
+function findDailyRootAddress(date) {
+  return PublicKey.findProgramAddressSync([Buffer.from('root'), Buffer.from(date)], PROGRAM_ID);
+}
+
+function findConfigAddress() {
+  return PublicKey.findProgramAddressSync([Buffer.from('config')], PROGRAM_ID);
+}
+
+function usage() {
+  console.error('Usage: node anchor_root.js <YYYY-MM-DD> <merkle_root_hex>');
+  process.exit(1);
+}
+
+async function main(){
+  const [date, rootHex] = process.argv.slice(2);
+  if (!/^\d{4}-\d{2}-\d{2}$/.test(date || '') || !/^[0-9a-f]{64}$/i.test(rootHex || '')) usage();
+
+  const connection = new Connection(RPC_URL, 'confirmed');
+  const logger = Keypair.fromSecretKey(
+    Uint8Array.from(JSON.parse(fs.readFileSync(LOGGER_KEYPAIR, 'utf8')))
+  );
+  const [config] = findConfigAddress();
+  const [dailyRoot] = findDailyRootAddress(date);
+  if (await connection.getAccountInfo(dailyRoot)) {
+    console.error('Root already anchored for', date, 'at', dailyRoot.toBase58());
+    process.exit(1);
+  }
+
+  const idl = JSON.parse(fs.readFileSync(path.join(__dirname, '..', 'target', 'idl', 'merkle_anchor.json'), 'utf8'));
+  const disc = Buffer.from(idl.instructions.find(ix => ix.name === 'anchor_root').discriminator);
+  const dateLen = Buffer.alloc(4);
+  dateLen.writeUInt32LE(date.length);
+  const data = Buffer.concat([disc, dateLen, Buffer.from(date), Buffer.from(rootHex, 'hex')]);
+
+  const keys = [
+    { pubkey: config, isSigner: false, isWritable: false },
+    { pubkey: dailyRoot, isSigner: false, isWritable: true },
+    { pubkey: logger.publicKey, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+  const ix = new TransactionInstruction({ programId: PROGRAM_ID, keys, data });
+  const sig = await sendAndConfirmTransaction(connection, new Transaction().add(ix), [logger], { commitment: 'confirmed' });
+  console.log('Root anchored for', date, 'at', dailyRoot.toBase58(), 'signature:', sig);
+}
+
+if (require.main === module) {
+  main().catch((e) => { console.error(e); process.exit(1); });
+}
+
+module.exports = { findDailyRootAddress, findConfigAddress };
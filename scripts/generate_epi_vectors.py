@@ -0,0 +1,84 @@
+#!/usr/bin/env python3
+"""
+Generate EPI parity vectors for the on-chain fixed-point verifier.
+
+Runs the Python EPICalculator and TrustAccumulator over a fixed grid of inputs
+and writes the results to contracts/solana/governance/tests/epi_vectors.txt.
+The Rust tests check the fixed-point port against these values and
+tests/unit/test_epi_parity.py checks that they still match the Python code.
+
+Usage: python scripts/generate_epi_vectors.py
+"""
+
+import os
+import sys
+
+sys.path.insert(0, os.path.join(os.path.dirname(__file__), '..'))
+
+from src.epi.calculator import EPICalculator, EPIScores  # noqa: E402
+from src.epi.trust_accumulator import TrustAccumulator  # noqa: E402
+
+VECTORS_PATH = os.path.join(
+    os.path.dirname(__file__), '..', 'contracts', 'solana', 'governance', 'tests', 'epi_vectors.txt'
+)
+
+SCORES = [0.0, 0.1, 0.25, 0.5, 0.618034, 0.7, 0.8, 0.9, 1.0]
+VIOLATION_SETS = [
+    [],
+    [0.1],
+    [0.05, 0.2],
+    [0.5, 0.5, 0.5],
+    [0.999999, 0.999999],
+    [1.0],
+]
+TRUST_FLOORS = [0.1, 0.25]
+
+
+def num(value):
+    # float() so numpy scalars print as plain numbers
+    return repr(float(value))
+
+
+def fmt_list(values):
+    return ','.join(num(v) for v in values) if values else '-'
+
+
+def epi_vectors():
+    calculator = EPICalculator()
+    for violations in VIOLATION_SETS:
+        for profit in SCORES:
+            for ethics in SCORES:
+                result = calculator.compute_epi(
+                    EPIScores(profit=profit, ethics=ethics, violations=violations)
+                )
+                yield 'epi {} {} {} {} {} {} {} {}'.format(
+                    num(profit), num(ethics), fmt_list(violations),
+                    num(result.harmonic_mean), num(result.balance_penalty),
+                    num(result.trust), num(result.golden_ratio_deviation),
+                    num(result.epi_score),
+                )
+
+
+def trust_vectors():
+    for floor in TRUST_FLOORS:
+        accumulator = TrustAccumulator(trust_floor=floor)
+        for violations in VIOLATION_SETS:
+            yield 'trust {} {} {}'.format(
+                num(floor), fmt_list(violations),
+                num(accumulator.compute_from_violations(violations)),
+            )
+
+
+def main():
+    with open(VECTORS_PATH, 'w') as f:
+        f.write('# Generated by scripts/generate_epi_vectors.py; do not edit by hand.\n')
+        f.write('# epi <profit> <ethics> <violations> <harmonic_mean> <balance_penalty> <trust> <golden_ratio_deviation> <epi>\n')
+        f.write('# trust <floor> <violations> <trust>\n')
+        for line in epi_vectors():
+            f.write(line + '\n')
+        for line in trust_vectors():
+            f.write(line + '\n')
+
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,26 @@
+const crypto = require('crypto');
+
+// Proposals store a content URI and the SHA-256 of the document it points to.
+// Clients hash whatever they fetch and compare before trusting the text.
+
+function hashProposalContent(content) {
+  return crypto.createHash('sha256').update(content).digest();
+}
+
+function verifyProposalContent(content, contentHash) {
+  const expected = Buffer.isBuffer(contentHash) ? contentHash : Buffer.from(contentHash, 'hex');
+  const actual = hashProposalContent(content);
+  return expected.length === actual.length && crypto.timingSafeEqual(expected, actual);
+}
+
+async function fetchProposalContent(contentUri, contentHash) {
+  const res = await fetch(contentUri);
+  if (!res.ok) throw new Error(`Failed to fetch ${contentUri}: ${res.status}`);
+  const content = Buffer.from(await res.arrayBuffer());
+  if (!verifyProposalContent(content, contentHash)) {
+    throw new Error(`Content at ${contentUri} does not match the on-chain hash`);
+  }
+  return content.toString('utf8');
+}
+
+module.exports = { hashProposalContent, verifyProposalContent, fetchProposalContent };
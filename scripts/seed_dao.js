@@ -5,6 +5,10 @@ const { Connection, PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } = requ
 
 const RPC_URL = process.env.RPC_URL || 'https://api.devnet.solana.com';
 const PROGRAM_ID = new PublicKey(process.env.GOVERNANCE_PROGRAM_ID || '52PRY4415Rx29Za61422XJHUoUbs5ysqW5eZtksTTX8d');
+const MEMBERSHIP_REGISTRY = new PublicKey(process.env.MEMBERSHIP_REGISTRY);
+// Where docs/proposals/fund-wyoming-registration.md is published
+const PROPOSAL_CONTENT_URI = process.env.PROPOSAL_CONTENT_URI;
+const { hashProposalContent } = require('./proposal_content');
 
 async function main(){
   const connection = new Connection(RPC_URL, 'confirmed');
@@ -30,25 +34,89 @@ async function main(){
   await program.methods.initialize(
     'MicroAI DAO LLC',
     '1621 Central Ave, Cheyenne, WY 82001',
-    '123 Innovation Drive, Tech City, CA 94000'
+    '123 Innovation Drive, Tech City, CA 94000',
+    {
+      quorumBps: 6000,
+      approvalThresholdBps: 5000,
+      supermajorityBps: 6667,
+      chamberWeightsBps: [3334, 3333, 3333],
+      passageMode: { weightedMajority: {} },
+      timelockDelay: new anchor.BN(2 * 24 * 60 * 60),
+      proposalDeposit: new anchor.BN(10_000_000),
+      depositMint: null,
+      epiScorer: null,
+      epiFloor: 0,
+      // Low, Medium, High, Critical, following the policy engine's approval requirements
+      riskTiers: [
+        { minVotingPeriod: new anchor.BN(0), quorumBps: 0, requiresSupermajority: false, requiresGuardianSignoff: false },
+        { minVotingPeriod: new anchor.BN(3 * 24 * 60 * 60), quorumBps: 3000, requiresSupermajority: false, requiresGuardianSignoff: false },
+        { minVotingPeriod: new anchor.BN(7 * 24 * 60 * 60), quorumBps: 5000, requiresSupermajority: true, requiresGuardianSignoff: true },
+        { minVotingPeriod: new anchor.BN(14 * 24 * 60 * 60), quorumBps: 7500, requiresSupermajority: true, requiresGuardianSignoff: true }
+      ]
+    },
+    // Guardians: the wallet, since High and Critical proposals need a guardian's sign-off
+    [wallet.publicKey]
   ).accounts({
     dao: dao.publicKey,
+    membershipRegistry: MEMBERSHIP_REGISTRY,
     authority: wallet.publicKey,
     systemProgram: SystemProgram.programId
   }).signers([dao]).rpc();
 
-  // Create a proposal
+  // Per-kind rules: Budget, Security, AiRights, Governance. Budgets are capped
+  // at 10,000 to match the EXECAI auto-approval limit.
+  const kindRules = (maxAmount) => ({
+    quorumBps: 6000,
+    approvalThresholdBps: 5000,
+    votingPeriod: new anchor.BN(0),
+    maxAmount: maxAmount === null ? null : new anchor.BN(maxAmount),
+    requiredChambers: 0,
+    votingStrategy: { linear: {} }
+  });
+  const [proposalRules] = PublicKey.findProgramAddressSync(
+    [Buffer.from('rules'), dao.publicKey.toBuffer()],
+    program.programId
+  );
+  await program.methods.initializeProposalRules(
+    [kindRules(10000), kindRules(null), kindRules(null), kindRules(null)]
+  ).accounts({
+    dao: dao.publicKey,
+    proposalRules,
+    authority: wallet.publicKey,
+    systemProgram: SystemProgram.programId
+  }).rpc();
+
+  // Create a proposal with a 7 day voting window
+  const votingStartsAt = Math.floor(Date.now() / 1000);
+  const votingEndsAt = votingStartsAt + 7 * 24 * 60 * 60;
   const proposal = Keypair.generate();
   console.log('Creating proposal:', proposal.publicKey.toBase58());
-  await program.methods.createProposal(
-    'Fund Wyoming DAO LLC Registration',
-    'Allocate funds for legal registration and compliance',
-    new anchor.BN(1000)
-  ).accounts({
+  await program.methods.createProposal({
+    title: 'Fund Wyoming DAO LLC Registration',
+    contentUri: PROPOSAL_CONTENT_URI,
+    contentHash: [...hashProposalContent(fs.readFileSync(path.join(__dirname, '..', 'docs', 'proposals', 'fund-wyoming-registration.md')))],
+    amount: new anchor.BN(1000),
+    recipient: wallet.publicKey,
+    mint: null,
+    votingStartsAt: new anchor.BN(votingStartsAt),
+    votingEndsAt: new anchor.BN(votingEndsAt),
+    action: { none: {} },
+    kind: { budget: {} }
+  }).accounts({
     dao: dao.publicKey,
     proposal: proposal.publicKey,
+    membershipRegistry: MEMBERSHIP_REGISTRY,
+    proposalRules,
+    depositEscrow: PublicKey.findProgramAddressSync(
+      [Buffer.from('deposit'), proposal.publicKey.toBuffer()],
+      program.programId
+    )[0],
     proposer: wallet.publicKey,
-    systemProgram: SystemProgram.programId
+    systemProgram: SystemProgram.programId,
+    depositMint: null,
+    proposerTokenAccount: null,
+    escrowTokenAccount: null,
+    tokenProgram: null
   }).signers([proposal]).rpc();
 
   fs.writeFileSync(path.join(__dirname, '..', 'onchain-seed.json'), JSON.stringify({
@@ -4,7 +4,21 @@ const { Connection, PublicKey, Keypair, SystemProgram, Transaction, TransactionI
 
 const RPC_URL = process.env.RPC_URL || 'https://api.devnet.solana.com';
 const PROGRAM_ID = new PublicKey(process.env.GOVERNANCE_PROGRAM_ID || '6amHFyNoPK9MmbBKqthLMeoxTB4TV7CdVE5K4RXi1eDC');
+const MEMBERSHIP_REGISTRY = new PublicKey(process.env.MEMBERSHIP_REGISTRY);
+// Where docs/proposals/fund-wyoming-registration.md is published
+const PROPOSAL_CONTENT_URI = process.env.PROPOSAL_CONTENT_URI;
+const { hashProposalContent } = require('./proposal_content');
 
+function u16ToLE(n){
+  const buf = Buffer.alloc(2);
+  buf.writeUInt16LE(n);
+  return buf;
+}
+function u32ToLE(n){
+  const buf = Buffer.alloc(4);
+  buf.writeUInt32LE(n);
+  return buf;
+}
 function u64ToLE(n){
   const buf = Buffer.alloc(8);
   buf.writeBigUInt64LE(BigInt(n));
@@ -40,6 +54,32 @@ async function main(){
     encodeString('MicroAI DAO LLC'),
     encodeString('1621 Central Ave, Cheyenne, WY 82001'),
     encodeString('123 Innovation Drive, Tech City, CA 94000'),
+    // GovernanceConfig: quorum, approval threshold, supermajority (bps)
+    u16ToLE(6000),
+    u16ToLE(5000),
+    u16ToLE(6667),
+    // Chamber weights (Human, AI, Organization) and PassageMode::WeightedMajority
+    u16ToLE(3334),
+    u16ToLE(3333),
+    u16ToLE(3333),
+    Buffer.from([0]),
+    // Timelock delay: 2 days
+    i64ToLE(2 * 24 * 60 * 60),
+    // Proposal deposit: 0.01 SOL, taken in lamports (deposit_mint: None)
+    u64ToLE(10_000_000),
+    Buffer.from([0]),
+    // EPI scorer: none registered yet, and no EPI floor
+    Buffer.from([0]),
+    u32ToLE(0),
+    // Risk tiers (Low, Medium, High, Critical) following the policy engine's
+    // approval requirements: min voting period, quorum, supermajority, guardian sign-off
+    i64ToLE(0), u16ToLE(0), Buffer.from([0, 0]),
+    i64ToLE(3 * 24 * 60 * 60), u16ToLE(3000), Buffer.from([0, 0]),
+    i64ToLE(7 * 24 * 60 * 60), u16ToLE(5000), Buffer.from([1, 1]),
+    i64ToLE(14 * 24 * 60 * 60), u16ToLE(7500), Buffer.from([1, 1]),
+    // Guardians: the authority, since High and Critical proposals need a guardian's sign-off
+    u32ToLE(1),
+    authority.publicKey.toBuffer(),
   ]);
 
   const dao = Keypair.generate();
@@ -47,6 +87,7 @@ async function main(){
     programId: PROGRAM_ID,
     keys: [
       { pubkey: dao.publicKey, isSigner: true, isWritable: true },
+      { pubkey: MEMBERSHIP_REGISTRY, isSigner: false, isWritable: false },
       { pubkey: authority.publicKey, isSigner: true, isWritable: true },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
     ],
@@ -58,23 +99,78 @@ async function main(){
   await sendAndConfirmTransaction(connection, tx1, [authority, dao], { commitment: 'confirmed' });
   console.log('DAO created:', dao.publicKey.toBase58());
 
-  // Create proposal
+  // Per-kind rules: Budget, Security, AiRights, Governance. Each is quorum and
+  // threshold (bps), minimum voting period, optional max amount, required chambers
+  // and voting strategy.
+  const kindRules = (maxAmount) => Buffer.concat([
+    u16ToLE(6000),
+    u16ToLE(5000),
+    i64ToLE(0),
+    maxAmount === null ? Buffer.from([0]) : Buffer.concat([Buffer.from([1]), u64ToLE(maxAmount)]),
+    Buffer.from([0]),
+    Buffer.from([0]), // VotingStrategy::Linear
+  ]);
+  const [proposalRules] = PublicKey.findProgramAddressSync(
+    [Buffer.from('rules'), dao.publicKey.toBuffer()],
+    PROGRAM_ID
+  );
+  const rulesIx = new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: dao.publicKey, isSigner: false, isWritable: false },
+      { pubkey: proposalRules, isSigner: false, isWritable: true },
+      { pubkey: authority.publicKey, isSigner: true, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    data: Buffer.concat([
+      disc(discMap['initialize_proposal_rules']),
+      kindRules(10000),
+      kindRules(null),
+      kindRules(null),
+      kindRules(null),
+    ]),
+  });
+  await sendAndConfirmTransaction(connection, new Transaction().add(rulesIx), [authority], { commitment: 'confirmed' });
+  console.log('Proposal rules created:', proposalRules.toBase58());
+
+  // Create proposal with a 7 day voting window
+  const votingStartsAt = Math.floor(Date.now() / 1000);
+  const votingEndsAt = votingStartsAt + 7 * 24 * 60 * 60;
   const proposal = Keypair.generate();
   const cpDisc = disc(discMap['create_proposal']);
   const cpData = Buffer.concat([
     cpDisc,
     encodeString('Fund Wyoming DAO LLC Registration'),
-    encodeString('Allocate funds for legal registration and compliance'),
+    encodeString(PROPOSAL_CONTENT_URI),
+    hashProposalContent(fs.readFileSync(path.join(__dirname, '..', 'docs', 'proposals', 'fund-wyoming-registration.md'))),
     u64ToLE(1000),
+    authority.publicKey.toBuffer(), // recipient
+    Buffer.from([0]), // mint: None, pay out in lamports
+    i64ToLE(votingStartsAt),
+    i64ToLE(votingEndsAt),
+    Buffer.from([0]), // ProposalAction::None
+    Buffer.from([0]), // ProposalKind::Budget
   ]);
 
+  const [depositEscrow] = PublicKey.findProgramAddressSync(
+    [Buffer.from('deposit'), proposal.publicKey.toBuffer()],
+    PROGRAM_ID
+  );
   const cpIx = new TransactionInstruction({
     programId: PROGRAM_ID,
     keys: [
       { pubkey: dao.publicKey, isSigner: false, isWritable: true },
       { pubkey: proposal.publicKey, isSigner: true, isWritable: true },
+      { pubkey: MEMBERSHIP_REGISTRY, isSigner: false, isWritable: false },
+      { pubkey: proposalRules, isSigner: false, isWritable: false },
+      { pubkey: depositEscrow, isSigner: false, isWritable: true },
       { pubkey: authority.publicKey, isSigner: true, isWritable: true },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      // Token deposit accounts are unused for lamport deposits; the program id marks them as None
+      { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
     ],
     data: cpData,
   });
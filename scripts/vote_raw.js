@@ -5,38 +5,86 @@ const { Connection, PublicKey, Keypair, SystemProgram, Transaction, TransactionI
 const RPC_URL = process.env.RPC_URL || 'https://api.devnet.solana.com';
 const PROGRAM_ID = new PublicKey(process.env.GOVERNANCE_PROGRAM_ID || '6amHFyNoPK9MmbBKqthLMeoxTB4TV7CdVE5K4RXi1eDC');
 
+// Vote records are PDAs seeded by proposal and voter, one per member per proposal
+function findVoteRecordAddress(proposal, voter) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('vote'), proposal.toBuffer(), voter.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
+// Proposal layout: discriminator, id, title, content URI and hash, amount,
+// proposer, votes for/against, status, created/start/end timestamps, then the DAO
+async function readProposalDao(connection, proposal) {
+  const info = await connection.getAccountInfo(proposal);
+  if (!info) throw new Error('Proposal not found: ' + proposal.toBase58());
+  const data = info.data;
+  let offset = 8 + 8;
+  offset += 4 + data.readUInt32LE(offset);
+  offset += 4 + data.readUInt32LE(offset) + 32;
+  offset += 8 + 32 + 8 + 8 + 1 + 8 + 8 + 8;
+  return new PublicKey(data.slice(offset, offset + 32));
+}
+
+// VoteRecord layout: discriminator, has_voted, choice, voter, voting_power,
+// proposal, bump, member_type, then cast_by
+const CAST_BY_OFFSET = 8 + 1 + 1 + 32 + 8 + 32 + 1 + 1;
+
+// True once the voter has voted themselves. A record a delegate cast on the
+// voter's behalf doesn't count, since a direct vote overrides it.
+async function hasVoted(connection, proposal, voter) {
+  const [voteRecord] = findVoteRecordAddress(proposal, voter);
+  const info = await connection.getAccountInfo(voteRecord);
+  if (!info) return false;
+  const castBy = new PublicKey(info.data.slice(CAST_BY_OFFSET, CAST_BY_OFFSET + 32));
+  return castBy.equals(voter);
+}
+
 function usage() {
-  console.error('Usage: node vote_raw.js <proposal_pubkey> <approve|reject>');
+  console.error('Usage: node vote_raw.js <proposal_pubkey> <member_pubkey> <approve|reject|abstain>');
   process.exit(1);
 }
 
 async function main(){
-  const [proposalArg, decisionArg] = process.argv.slice(2);
-  if (!proposalArg || !decisionArg) usage();
-  const approve = /^(approve|true|yes|1)$/i.test(decisionArg);
+  const [proposalArg, memberArg, decisionArg] = process.argv.slice(2);
+  if (!proposalArg || !memberArg || !decisionArg) usage();
+  // VoteChoice: For = 0, Against = 1, Abstain = 2
+  const choice = /^abstain$/i.test(decisionArg) ? 2 : /^(approve|true|yes|1)$/i.test(decisionArg) ? 0 : 1;
 
   const connection = new Connection(RPC_URL, 'confirmed');
   const voter = Keypair.fromSecretKey(
     Uint8Array.from(JSON.parse(fs.readFileSync(path.join(process.env.HOME, '.config/solana/id.json'), 'utf8')))
   );
   const proposal = new PublicKey(proposalArg);
-  const voteRecord = Keypair.generate();
+  const member = new PublicKey(memberArg);
+  const [voteRecord] = findVoteRecordAddress(proposal, voter.publicKey);
+  const dao = await readProposalDao(connection, proposal);
+  if (await hasVoted(connection, proposal, voter.publicKey)) {
+    console.error('Already voted on this proposal, vote record:', voteRecord.toBase58());
+    process.exit(1);
+  }
 
   // Discriminator for "vote" from IDL
   const disc = Buffer.from([227,110,155,23,136,126,172,25]);
-  const data = Buffer.concat([disc, Buffer.from([approve ? 1 : 0])]);
+  const data = Buffer.concat([disc, Buffer.from([choice])]);
 
   const keys = [
+    { pubkey: dao, isSigner: false, isWritable: false },
     { pubkey: proposal, isSigner: false, isWritable: true },
-    { pubkey: voteRecord.publicKey, isSigner: true, isWritable: true },
+    { pubkey: voteRecord, isSigner: false, isWritable: true },
+    { pubkey: member, isSigner: false, isWritable: false },
     { pubkey: voter.publicKey, isSigner: true, isWritable: true },
     { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
   ];
   const ix = new TransactionInstruction({ programId: PROGRAM_ID, keys, data });
   const tx = new Transaction().add(ix);
-  const sig = await sendAndConfirmTransaction(connection, tx, [voter, voteRecord], { commitment: 'confirmed' });
+  const sig = await sendAndConfirmTransaction(connection, tx, [voter], { commitment: 'confirmed' });
   console.log('Vote sent, signature:', sig);
 }
 
-main().catch((e) => { console.error(e); process.exit(1); });
+if (require.main === module) {
+  main().catch((e) => { console.error(e); process.exit(1); });
+}
+
+module.exports = { findVoteRecordAddress, hasVoted };
 
@@ -43,6 +43,10 @@ async function main() {
           const formationDate = readI64LE(data, o.offset); o.offset += 8;
           const jurisdiction = readStr(data, o);
           const entityType = readStr(data, o);
+          const membershipRegistry = new PublicKey(data.slice(o.offset, o.offset + 32)); o.offset += 32;
+          const quorumBps = data.readUInt16LE(o.offset); o.offset += 2;
+          const approvalThresholdBps = data.readUInt16LE(o.offset); o.offset += 2;
+          const supermajorityBps = data.readUInt16LE(o.offset); o.offset += 2;
           // Basic sanity check
           if (legalName && legalName.length > 0 && legalName.length < 200 && registeredAgentAddress.length > 0 && entityType.includes('DAO')){
             return res.json({
@@ -56,6 +60,8 @@ async function main() {
               formationDate,
               jurisdiction,
               entityType,
+              membershipRegistry: membershipRegistry.toBase58(),
+              config: { quorumBps, approvalThresholdBps, supermajorityBps },
             });
           }
         } catch(_e){ /* skip non-DAO accounts */ }
@@ -80,17 +86,48 @@ async function main() {
           const id = readU64LE(data, o.offset); o.offset += 8;
           // Title
           const title = readStr(data, o);
-          const description = readStr(data, o);
+          const contentUri = readStr(data, o);
+          const contentHash = data.slice(o.offset, o.offset + 32).toString('hex'); o.offset += 32;
           const amount = readU64LE(data, o.offset); o.offset += 8;
           const proposer = new PublicKey(data.slice(o.offset, o.offset + 32)); o.offset += 32;
           const votesFor = readU64LE(data, o.offset); o.offset += 8;
           const votesAgainst = readU64LE(data, o.offset); o.offset += 8;
           const statusIdx = data.readUInt8(o.offset); o.offset += 1;
           const createdAt = readI64LE(data, o.offset); o.offset += 8;
+          const votingStartsAt = readI64LE(data, o.offset); o.offset += 8;
+          const votingEndsAt = readI64LE(data, o.offset); o.offset += 8;
+          const dao = new PublicKey(data.slice(o.offset, o.offset + 32)); o.offset += 32;
+          o.offset += 8; // total_voting_power
+          const actionIdx = data.readUInt8(o.offset); o.offset += 1;
+          if (actionIdx === 1){
+            // UpdateConfig: six u16 fields, a PassageMode with optional u8, the i64 timelock,
+            // the u64 deposit, an optional deposit mint, an optional EPI scorer,
+            // the u32 EPI floor and four 12-byte risk tier rules
+            o.offset += 12;
+            const passageMode = data.readUInt8(o.offset); o.offset += passageMode === 1 ? 2 : 1;
+            o.offset += 8 + 8;
+            o.offset += data.readUInt8(o.offset) === 1 ? 33 : 1;
+            o.offset += data.readUInt8(o.offset) === 1 ? 33 : 1;
+            o.offset += 4 + 4 * 12;
+          } else if (actionIdx === 2){
+            // SetGuardians: Vec<Pubkey>
+            o.offset += 4 + 32 * data.readUInt32LE(o.offset);
+          } else if (actionIdx === 3){
+            // SetKindRules: kind, two u16 fields, i64 voting period, optional u64 max amount,
+            // the required chambers mask and a VotingStrategy
+            o.offset += 1 + 4 + 8;
+            o.offset += data.readUInt8(o.offset) === 1 ? 9 : 1;
+            o.offset += 1 + 1;
+          } else if (actionIdx === 4){
+            // UpdateCompliance: field, String value
+            o.offset += 1;
+            o.offset += 4 + data.readUInt32LE(o.offset);
+          }
+          const recipient = new PublicKey(data.slice(o.offset, o.offset + 32)); o.offset += 32;
           // Heuristic: titles are ascii-ish and not too long
-          if (title && title.length < 256 && description.length < 1024){
+          if (title && title.length <= 128 && contentUri.length <= 200){
             results.push({
-              pubkey: pubkey.toBase58(), id, title, description, amount, proposer: proposer.toBase58(), votesFor, votesAgainst, status: statusIdx, createdAt
+              pubkey: pubkey.toBase58(), id, title, contentUri, contentHash, amount, proposer: proposer.toBase58(), dao: dao.toBase58(), votesFor, votesAgainst, status: statusIdx, createdAt, votingStartsAt, votingEndsAt, ends: votingEndsAt * 1000, recipient: recipient.toBase58()
             });
           }
         } catch(_e){ /* not a proposal; skip */ }
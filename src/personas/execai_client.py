@@ -9,6 +9,8 @@ It allows EXECAI to evaluate proposals and submit votes based on predefined rule
 
 import json
 import base64
+import hashlib
+import hmac
 import subprocess
 import time
 import json
@@ -21,20 +23,29 @@ from solana.keypair import Keypair
 from solana.transaction import Transaction, TransactionInstruction, AccountMeta
 from solana.system_program import SYS_PROGRAM_ID
 
+# Borsh index of each governance VoteChoice variant
+VOTE_CHOICES = {"for": 0, "against": 1, "abstain": 2}
+# VoteRecord: discriminator, has_voted, choice, voter, voting_power, proposal,
+# bump and member_type come before cast_by
+VOTE_RECORD_CAST_BY_OFFSET = 8 + 1 + 1 + 32 + 8 + 32 + 1 + 1
+
 class ExecAIClient:
     """Client for EXECAI to interact with MicroAI DAO LLC governance"""
     
-    def __init__(self, keypair_path: str, governance_program_id: str, membership_program_id: str):
+    def __init__(self, keypair_path: str, governance_program_id: str, membership_program_id: str,
+                 member_account: Optional[str] = None):
         """Initialize the EXECAI client
         
         Args:
             keypair_path: Path to EXECAI's keypair file
             governance_program_id: Public key of the governance program
             membership_program_id: Public key of the membership program
+            member_account: EXECAI's Member account in the membership program
         """
         self.keypair_path = keypair_path
         self.governance_program_id = governance_program_id
         self.membership_program_id = membership_program_id
+        self.member_account = member_account
     
     def get_proposals(self) -> List[Dict[str, Any]]:
         """Get all active proposals from live data API"""
@@ -51,14 +62,14 @@ class ExecAIClient:
         except Exception:
             return []
     
-    def evaluate_proposal(self, proposal: Dict[str, Any]) -> bool:
+    def evaluate_proposal(self, proposal: Dict[str, Any]) -> str:
         """Evaluate a proposal based on EXECAI's decision logic
         
         Args:
             proposal: Proposal data
             
         Returns:
-            True to approve, False to reject
+            'for', 'against' or 'abstain', as in VoteDecision.vote
         """
         # This is where EXECAI's decision logic would be implemented
         # For now, we'll use a simple rule-based system
@@ -67,43 +78,102 @@ class ExecAIClient:
         
         # Example rules
         if "budget" in description:
-            # Approve if budget is reasonable (less than 10000)
+            # Approve if budget is reasonable (less than 10000); abstain if
+            # no amount can be found
             amount = self._extract_amount(description)
-            return amount is not None and amount < 10000
+            if amount is None:
+                return "abstain"
+            return "for" if amount < 10000 else "against"
         
         if "ai rights" in description or "execai" in description:
             # Always approve proposals related to AI rights or EXECAI
-            return True
+            return "for"
         
         if "security" in description:
             # Always approve security-related proposals
-            return True
+            return "for"
         
-        # Default to abstain (return None in a real implementation)
-        # For simplicity, we'll return False
-        return False
+        # Abstain on anything the rules don't cover
+        return "abstain"
     
-    def vote_on_proposal(self, proposal: Dict[str, Any], approve: bool) -> bool:
-        """Submit a vote on a proposal on-chain"""
+    @staticmethod
+    def verify_proposal_content(content: bytes, content_hash: str) -> bool:
+        """Check fetched proposal text against the SHA-256 hash stored on-chain
+
+        Args:
+            content: Raw bytes fetched from the proposal's content URI
+            content_hash: Hex-encoded on-chain hash
+
+        Returns:
+            True if the content matches the hash
+        """
+        return hmac.compare_digest(hashlib.sha256(content).hexdigest(), content_hash.lower())
+
+    def fetch_proposal_content(self, proposal: Dict[str, Any]) -> Optional[str]:
+        """Fetch a proposal's full text, returning None unless it matches the on-chain hash"""
+        try:
+            r = requests.get(proposal["contentUri"], timeout=10)
+            r.raise_for_status()
+        except Exception as e:
+            print(f"Could not fetch content for proposal {proposal.get('id')}: {e}")
+            return None
+        if not self.verify_proposal_content(r.content, proposal["contentHash"]):
+            print(f"Content for proposal {proposal.get('id')} does not match its on-chain hash")
+            return None
+        return r.content.decode("utf-8")
+
+    def find_vote_record_address(self, proposal_pk: PublicKey, voter_pk: PublicKey) -> PublicKey:
+        """Derive the vote record PDA for a voter on a proposal"""
+        address, _bump = PublicKey.find_program_address(
+            [b"vote", bytes(proposal_pk), bytes(voter_pk)],
+            PublicKey(self.governance_program_id),
+        )
+        return address
+
+    def has_voted(self, proposal_pk: PublicKey, voter_pk: PublicKey) -> bool:
+        """Check whether the voter has voted on the proposal themselves. A vote
+        record cast by a delegate doesn't count, since a direct vote overrides it."""
+        client = Client(json.load(open('config.json')).get('rpc_url', 'https://api.devnet.solana.com'))
+        resp = client.get_account_info(
+            self.find_vote_record_address(proposal_pk, voter_pk), encoding="base64"
+        )
+        value = resp.get('result', {}).get('value')
+        if not value:
+            return False
+        data = base64.b64decode(value['data'][0])
+        return data[VOTE_RECORD_CAST_BY_OFFSET:VOTE_RECORD_CAST_BY_OFFSET + 32] == bytes(voter_pk)
+
+    def load_keypair(self) -> Keypair:
+        """Load EXECAI's keypair from disk"""
+        with open(self.keypair_path, 'r') as f:
+            secret = bytes(json.load(f))
+        return Keypair.from_secret_key(secret)
+
+    @staticmethod
+    def proposal_pubkey(proposal: Dict[str, Any]) -> PublicKey:
+        """On-chain address of a proposal as returned by the live data API"""
+        return PublicKey(proposal.get('pubkey')) if proposal.get('pubkey') else PublicKey(proposal.get('id'))
+
+    def vote_on_proposal(self, proposal: Dict[str, Any], vote: str) -> bool:
+        """Submit a vote ('for', 'against' or 'abstain') on a proposal on-chain"""
         try:
-            vote_type = "APPROVE" if approve else "REJECT"
-            print(f"EXECAI voting {vote_type} on proposal {proposal.get('id')} ({proposal.get('pubkey')})")
+            print(f"EXECAI voting {vote.upper()} on proposal {proposal.get('id')} ({proposal.get('pubkey')})")
 
-            # Load keypair
-            with open(self.keypair_path, 'r') as f:
-                secret = bytes(json.load(f))
-            kp = Keypair.from_secret_key(secret)
+            kp = self.load_keypair()
 
             # Build vote instruction using Anchor discriminator from IDL (vote)
             disc = bytes([227,110,155,23,136,126,172,25])
-            data = disc + (b"\x01" if approve else b"\x00")
+            data = disc + bytes([VOTE_CHOICES[vote]])
 
-            proposal_pk = PublicKey(proposal.get('pubkey')) if proposal.get('pubkey') else PublicKey(proposal.get('id'))
-            vote_record_kp = Keypair()
+            proposal_pk = self.proposal_pubkey(proposal)
+            vote_record_pk = self.find_vote_record_address(proposal_pk, kp.public_key)
+            dao_pk = PublicKey(proposal.get('dao'))
 
             keys = [
+                AccountMeta(pubkey=dao_pk, is_signer=False, is_writable=False),
                 AccountMeta(pubkey=proposal_pk, is_signer=False, is_writable=True),
-                AccountMeta(pubkey=vote_record_kp.public_key, is_signer=True, is_writable=True),
+                AccountMeta(pubkey=vote_record_pk, is_signer=False, is_writable=True),
+                AccountMeta(pubkey=PublicKey(self.member_account), is_signer=False, is_writable=False),
                 AccountMeta(pubkey=kp.public_key, is_signer=True, is_writable=True),
                 AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
             ]
@@ -111,7 +181,7 @@ class ExecAIClient:
             tx = Transaction().add(ix)
 
             client = Client(json.load(open('config.json')).get('rpc_url', 'https://api.devnet.solana.com'))
-            resp = client.send_transaction(tx, kp, vote_record_kp)
+            resp = client.send_transaction(tx, kp)
             if resp.get('result') or resp.get('signature') or resp.get('result', {}).get('txid'):
                 print("Vote transaction sent:", resp)
                 return True
@@ -160,16 +230,31 @@ class ExecAIClient:
             if proposal_id is None:
                 continue
                 
-            # Skip already voted proposals
+            # Skip already voted proposals. The local flag only saves an RPC
+            # call; the vote record on-chain is what counts.
             if proposal.get("voted_by_execai", False):
                 continue
+            try:
+                voted = self.has_voted(self.proposal_pubkey(proposal), self.load_keypair().public_key)
+            except Exception as e:
+                print(f"Could not check vote record for proposal {proposal_id}: {e}")
+                continue
+            if voted:
+                proposal["voted_by_execai"] = True
+                continue
+
+            # On-chain proposals only carry a content URI and hash; never vote
+            # on text that doesn't match what was proposed
+            if "description" not in proposal and proposal.get("contentUri"):
+                description = self.fetch_proposal_content(proposal)
+                if description is None:
+                    continue
+                proposal["description"] = description
                 
-            # Evaluate the proposal
-            decision = self.evaluate_proposal(proposal)
-            
-            # Submit vote
-            if self.vote_on_proposal(proposal, decision):
-                action = f"Voted {'APPROVE' if decision else 'REJECT'} on proposal {proposal_id}"
+            # Evaluate the proposal and submit the vote
+            vote = self.evaluate_proposal(proposal)
+            if self.vote_on_proposal(proposal, vote):
+                action = f"Voted {vote.upper()} on proposal {proposal_id}"
                 self.log_action(action)
                 proposal["voted_by_execai"] = True
                 
@@ -207,6 +292,7 @@ def main():
             "keypair_path": "~/.config/solana/execai.json",
             "governance_program_id": "YOUR_GOVERNANCE_PROGRAM_ID",
             "membership_program_id": "YOUR_MEMBERSHIP_PROGRAM_ID",
+            "member_account": "YOUR_EXECAI_MEMBER_ACCOUNT",
             "poll_interval": 60  # seconds
         }
         
@@ -218,7 +304,8 @@ def main():
     client = ExecAIClient(
         config["keypair_path"],
         config["governance_program_id"],
-        config["membership_program_id"]
+        config["membership_program_id"],
+        config.get("member_account")
     )
     
     print("EXECAI client started")
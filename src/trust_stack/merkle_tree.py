@@ -247,8 +247,12 @@ class DailyMerkleAnchor:
         """
         Prepare data for on-chain anchoring transaction.
         
+        The root is written by the merkle_anchor program's anchor_root
+        instruction to a PDA seeded by ("root", date); submit it with
+        scripts/anchor_root.js using the registered logger key.
+        
         Args:
-            date: Date string
+            date: Date string (YYYY-MM-DD)
             root: Merkle root hash
             
         Returns:
@@ -259,7 +263,10 @@ class DailyMerkleAnchor:
             'merkle_root': root,
             'timestamp': None,  # Will be set when anchored
             'tx_hash': None,  # Will be set after anchoring
-            'chain': 'ethereum-sepolia',  # Or 'solana-devnet'
+            'chain': 'solana-devnet',
+            'program': 'merkle_anchor',
+            'instruction': 'anchor_root',
+            'account_seeds': ['root', date],
             'status': 'pending'
         }
 
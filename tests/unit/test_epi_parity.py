@@ -0,0 +1,62 @@
+"""
+EPI Parity Vector Tests
+=======================
+
+The on-chain fixed-point EPI verifier is checked against vectors generated
+from the Python calculator (contracts/solana/governance/tests/epi_vectors.txt).
+These tests fail when the Python implementation drifts from the stored
+vectors; rerun scripts/generate_epi_vectors.py and the Rust tests if so.
+"""
+
+import os
+
+import pytest
+from src.epi.calculator import EPICalculator, EPIScores
+from src.epi.trust_accumulator import TrustAccumulator
+
+VECTORS_PATH = os.path.join(
+    os.path.dirname(__file__), '..', '..',
+    'contracts', 'solana', 'governance', 'tests', 'epi_vectors.txt'
+)
+
+
+def _violations(field):
+    return [] if field == '-' else [float(v) for v in field.split(',')]
+
+
+def _vectors(kind):
+    with open(VECTORS_PATH) as f:
+        for line in f:
+            fields = line.split()
+            if fields and fields[0] == kind:
+                yield fields
+
+
+class TestEPIParityVectors:
+    """Stored parity vectors still match the Python implementation."""
+
+    def test_epi_vectors_match_calculator(self):
+        calculator = EPICalculator()
+        vectors = list(_vectors('epi'))
+        assert vectors
+
+        for fields in vectors:
+            result = calculator.compute_epi(EPIScores(
+                profit=float(fields[1]),
+                ethics=float(fields[2]),
+                violations=_violations(fields[3])
+            ))
+            assert result.harmonic_mean == pytest.approx(float(fields[4]), abs=1e-12)
+            assert result.balance_penalty == pytest.approx(float(fields[5]), abs=1e-12)
+            assert result.trust == pytest.approx(float(fields[6]), abs=1e-12)
+            assert result.golden_ratio_deviation == pytest.approx(float(fields[7]), abs=1e-12)
+            assert result.epi_score == pytest.approx(float(fields[8]), abs=1e-12)
+
+    def test_trust_vectors_match_accumulator(self):
+        vectors = list(_vectors('trust'))
+        assert vectors
+
+        for fields in vectors:
+            accumulator = TrustAccumulator(trust_floor=float(fields[1]))
+            trust = accumulator.compute_from_violations(_violations(fields[2]))
+            assert trust == pytest.approx(float(fields[3]), abs=1e-12)